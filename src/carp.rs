@@ -0,0 +1,207 @@
+// How often the peer is queried. This is done on a separate thread so that a slow or unreachable peer doesn't hold up the dashboard.
+const PEER_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+// How long to wait before trying to connect to the peer again after failing to connect to or query it.
+const PEER_RECONNECT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug)]
+pub(crate) struct HighAvailability {
+	vips: Vec<Vip>,
+	pfsync_enabled: bool,
+
+	/// `None` if pfsync is not enabled in pfSense, or the router doesn't have a pfsync interface
+	pub(crate) pfsync: Option<crate::ssh_exec::ifconfig_a::Pfsync>,
+
+	peer: Option<Peer>,
+
+	/// The error from the most recent attempt to connect to or query the peer, as of the last update
+	peer_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Peer {
+	hostname: String,
+
+	/// Updated by the thread that polls the peer
+	latest: std::sync::Arc<std::sync::Mutex<PeerResult>>,
+}
+
+#[derive(Debug, Default)]
+struct PeerResult {
+	/// `None` if the peer couldn't be queried
+	carp: Option<Vec<crate::ssh_exec::ifconfig_a::Carp>>,
+
+	/// The error from the most recent attempt to connect to or query the peer, if it failed
+	error: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Vip {
+	config: crate::pfconfig::CarpVip,
+
+	/// `None` if the router doesn't have this VHID on the VIP's interface
+	pub(crate) state: Option<State>,
+
+	/// `None` if the peer isn't configured or couldn't be queried, or doesn't have this VHID
+	pub(crate) peer_state: Option<State>,
+}
+
+#[derive(Debug)]
+pub(crate) struct State {
+	/// Like `MASTER`, `BACKUP` or `INIT`
+	pub(crate) status: String,
+
+	pub(crate) advbase: u32,
+	pub(crate) advskew: u32,
+}
+
+impl HighAvailability {
+	pub(crate) fn new(
+		vips: impl IntoIterator<Item = crate::pfconfig::CarpVip>,
+		pfsync_enabled: bool,
+		config: crate::config::HighAvailability,
+		username: &str,
+	) -> Self {
+		let vips =
+			vips.into_iter()
+			.map(|config| Vip {
+				config,
+				state: None,
+				peer_state: None,
+			})
+			.collect();
+
+		let peer = config.peer.map(|hostname| Peer::spawn(hostname, username.to_owned()));
+
+		HighAvailability {
+			vips,
+			pfsync_enabled,
+			pfsync: None,
+			peer,
+			peer_error: None,
+		}
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.vips.is_empty()
+	}
+
+	pub(crate) fn vips(&self) -> impl Iterator<Item = &Vip> {
+		self.vips.iter()
+	}
+
+	pub(crate) fn has_peer(&self) -> bool {
+		self.peer.is_some()
+	}
+
+	/// The error from the most recent attempt to connect to or query the peer, if any
+	pub(crate) fn peer_error(&self) -> Option<(&str, &str)> {
+		let peer = self.peer.as_ref()?;
+		let error = self.peer_error.as_ref()?;
+		Some((&peer.hostname, error))
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		let crate::ssh_exec::ifconfig_a::Output { carp, pfsync } = crate::ssh_exec::ifconfig_a::run(session)?;
+		for vip in &mut self.vips {
+			vip.state = find_state(&carp, &vip.config);
+		}
+
+		self.pfsync = if self.pfsync_enabled { pfsync } else { None };
+
+		if let Some(peer) = &self.peer {
+			let latest = peer.latest.lock().expect("could not lock CARP peer result");
+			for vip in &mut self.vips {
+				vip.peer_state = latest.carp.as_ref().and_then(|peer_carp| find_state(peer_carp, &vip.config));
+			}
+			self.peer_error.clone_from(&latest.error);
+		}
+
+		Ok(())
+	}
+}
+
+impl Peer {
+	/// Starts a thread that polls the peer's CARP state forever.
+	fn spawn(hostname: String, username: String) -> Self {
+		let latest: std::sync::Arc<std::sync::Mutex<PeerResult>> = Default::default();
+
+		{
+			let latest = latest.clone();
+			let hostname = hostname.clone();
+
+			let _ = std::thread::spawn(move || {
+				let mut session = None;
+
+				loop {
+					// The peer being unreachable is something to show, not a reason to stop the dashboard.
+					// The previous error keeps being shown until the next attempt.
+					let result = query(&mut session, &hostname, &username);
+
+					let sleep_for = {
+						let mut latest = latest.lock().expect("could not lock CARP peer result");
+						match result {
+							Ok(carp) => {
+								*latest = PeerResult { carp: Some(carp), error: None };
+								PEER_POLL_INTERVAL
+							},
+
+							Err(err) => {
+								session = None;
+								*latest = PeerResult { carp: None, error: Some(err.0.to_string()) };
+								PEER_RECONNECT_INTERVAL
+							},
+						}
+					};
+
+					std::thread::sleep(sleep_for);
+				}
+			});
+		}
+
+		Peer {
+			hostname,
+			latest,
+		}
+	}
+}
+
+/// Connects to the peer if not already connected, and returns its CARP state.
+fn query(session: &mut Option<ssh2::Session>, hostname: &str, username: &str) -> Result<Vec<crate::ssh_exec::ifconfig_a::Carp>, crate::Error> {
+	let session = match session {
+		Some(session) => session,
+		None => session.insert(crate::connect(hostname, username, Some(5000))?),
+	};
+
+	let crate::ssh_exec::ifconfig_a::Output { carp, .. } = crate::ssh_exec::ifconfig_a::run(session)?;
+	Ok(carp)
+}
+
+impl Vip {
+	/// The VIP's description and address, or just its address if it doesn't have a description
+	pub(crate) fn label(&self) -> String {
+		match &self.config.description {
+			Some(description) => format!("{} ({})", description, self.config.address),
+			None => self.config.address.clone(),
+		}
+	}
+
+	pub(crate) fn vhid(&self) -> u8 {
+		self.config.vhid
+	}
+
+	/// Whether both nodes claim to be MASTER, such as when they can't see each other's advertisements.
+	pub(crate) fn is_split_brain(&self) -> bool {
+		let is_master = |state: &Option<State>| state.as_ref().is_some_and(|state| state.status == "MASTER");
+		is_master(&self.state) && is_master(&self.peer_state)
+	}
+}
+
+fn find_state(carp: &[crate::ssh_exec::ifconfig_a::Carp], config: &crate::pfconfig::CarpVip) -> Option<State> {
+	let carp = carp.iter().find(|carp| carp.interface == config.interface && carp.vhid == config.vhid)?;
+	Some(State {
+		status: carp.state.clone(),
+		advbase: carp.advbase,
+		advskew: carp.advskew,
+	})
+}
@@ -3,6 +3,23 @@ pub(crate) struct Config {
 	pub(crate) ssh: Ssh,
 
 	pub(crate) services: Option<Services>,
+
+	pub(crate) filesystems: Option<Filesystems>,
+
+	pub(crate) disks: Option<Disks>,
+
+	pub(crate) interfaces: Option<Interfaces>,
+
+	pub(crate) states: Option<States>,
+
+	pub(crate) routes: Option<Routes>,
+
+	pub(crate) dhcp_leases: Option<DhcpLeases>,
+
+	pub(crate) high_availability: Option<HighAvailability>,
+	pub(crate) firewall_rules: Option<FirewallRules>,
+
+	pub(crate) firewall_logs: Option<FirewallLogs>,
 }
 
 impl Config {
@@ -16,6 +33,235 @@ impl Config {
 	}
 }
 
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Filesystems {
+	#[serde(default = "default_filesystem_types")]
+	pub(crate) types: Vec<String>,
+
+	#[serde(default)]
+	pub(crate) include: Vec<String>,
+
+	#[serde(default)]
+	pub(crate) exclude: Vec<String>,
+
+	#[serde(default)]
+	pub(crate) thresholds: std::collections::BTreeMap<String, UsageThresholds>,
+}
+
+impl Default for Filesystems {
+	fn default() -> Self {
+		Filesystems {
+			types: default_filesystem_types(),
+			include: vec![],
+			exclude: vec![],
+			thresholds: Default::default(),
+		}
+	}
+}
+
+fn default_filesystem_types() -> Vec<String> {
+	vec!["ufs".to_owned()]
+}
+
+#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+pub(crate) struct UsageThresholds {
+	pub(crate) warning: f32,
+	pub(crate) critical: f32,
+}
+
+#[derive(Default, serde_derive::Deserialize)]
+pub(crate) struct Disks {
+	#[serde(default)]
+	pub(crate) exclude: Vec<String>,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Interfaces {
+	#[serde(default = "default_interface_minimum_speed")]
+	pub(crate) minimum_speed: u64,
+
+	#[serde(default)]
+	pub(crate) minimum_speeds: std::collections::BTreeMap<String, u64>,
+
+	#[serde(default)]
+	pub(crate) labels: std::collections::BTreeMap<String, String>,
+}
+
+impl Default for Interfaces {
+	fn default() -> Self {
+		Interfaces {
+			minimum_speed: default_interface_minimum_speed(),
+			minimum_speeds: Default::default(),
+			labels: Default::default(),
+		}
+	}
+}
+
+fn default_interface_minimum_speed() -> u64 {
+	1000
+}
+
+#[derive(Default, serde_derive::Deserialize)]
+pub(crate) struct States {
+	#[serde(default)]
+	pub(crate) top_talkers: usize,
+}
+
+#[derive(Default, serde_derive::Deserialize)]
+pub(crate) struct Routes {
+	#[serde(default)]
+	pub(crate) full_table: bool,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct DhcpLeases {
+	#[serde(default)]
+	pub(crate) expired: bool,
+}
+
+#[derive(Default, serde_derive::Deserialize)]
+pub(crate) struct HighAvailability {
+	#[serde(default)]
+	pub(crate) peer: Option<String>,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct FirewallRules {
+	#[serde(default = "default_firewall_rules_max_rules")]
+	pub(crate) max_rules: usize,
+
+	#[serde(default = "default_firewall_rules_max_tables")]
+	pub(crate) max_tables: usize,
+}
+
+fn default_firewall_rules_max_rules() -> usize {
+	10
+}
+
+fn default_firewall_rules_max_tables() -> usize {
+	10
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct FirewallLogs {
+	#[serde(default = "default_firewall_logs_capacity")]
+	pub(crate) capacity: usize,
+
+	pub(crate) max_rows: Option<usize>,
+
+	pub(crate) archive: Option<Archive>,
+
+	pub(crate) forward: Option<Forward>,
+
+	pub(crate) receive: Option<Receive>,
+
+	pub(crate) detection: Option<Detection>,
+
+	pub(crate) reverse_dns: Option<ReverseDns>,
+}
+
+impl Default for FirewallLogs {
+	fn default() -> Self {
+		FirewallLogs {
+			capacity: default_firewall_logs_capacity(),
+			max_rows: None,
+			archive: None,
+			forward: None,
+			receive: None,
+			detection: None,
+			reverse_dns: None,
+		}
+	}
+}
+
+fn default_firewall_logs_capacity() -> usize {
+	1000
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Archive {
+	pub(crate) path: std::path::PathBuf,
+
+	pub(crate) format: ArchiveFormat,
+
+	pub(crate) max_size: Option<u64>,
+
+	#[serde(default = "default_archive_max_files")]
+	pub(crate) max_files: usize,
+}
+
+fn default_archive_max_files() -> usize {
+	5
+}
+
+#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ArchiveFormat {
+	Csv,
+	Json,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Forward {
+	pub(crate) address: String,
+
+	pub(crate) protocol: SyslogProtocol,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Receive {
+	pub(crate) address: String,
+
+	pub(crate) protocol: SyslogProtocol,
+}
+
+#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SyslogProtocol {
+	Tcp,
+	Udp,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct Detection {
+	#[serde(default)]
+	pub(crate) default: DetectionThresholds,
+
+	#[serde(default)]
+	pub(crate) interfaces: std::collections::BTreeMap<String, DetectionThresholds>,
+}
+
+/// Thresholds that aren't set are inherited from `default`, or from the built-in defaults.
+#[derive(Default, serde_derive::Deserialize)]
+pub(crate) struct DetectionThresholds {
+	pub(crate) window: Option<u64>,
+
+	/// `0` disables the check
+	pub(crate) port_scan: Option<usize>,
+
+	/// `0` disables the check
+	pub(crate) brute_force: Option<usize>,
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct ReverseDns {
+	pub(crate) server: String,
+
+	#[serde(default = "default_reverse_dns_cache_size")]
+	pub(crate) cache_size: usize,
+
+	#[serde(default = "default_reverse_dns_timeout_ms")]
+	pub(crate) timeout_ms: u64,
+}
+
+fn default_reverse_dns_cache_size() -> usize {
+	1000
+}
+
+fn default_reverse_dns_timeout_ms() -> u64 {
+	2000
+}
+
 #[derive(serde_derive::Deserialize)]
 pub(crate) struct Services {
 	#[serde(default)]
@@ -1,56 +1,123 @@
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct Cpu {
-	total_previous: crate::c_ulong,
-	total: crate::c_ulong,
+// Number of values per CPU in kern.cp_time and kern.cp_times. Ref: <sys/resource.h>
+const CPUSTATES: usize = 5;
 
-	idle_previous: crate::c_ulong,
-	idle: crate::c_ulong,
+#[derive(Clone, Debug)]
+pub(crate) struct Cpu {
+	total: CpuTimes,
+	cores: Vec<CpuTimes>,
 }
 
 impl Cpu {
 	pub(crate) fn new() -> Self {
 		Cpu {
-			total_previous: 0,
-			total: 0,
-
-			idle_previous: 0,
-			idle: 0,
+			total: Default::default(),
+			cores: vec![],
 		}
 	}
 
+	/// Parses `kern.cp_time` followed by `kern.cp_times`
 	pub(crate) fn update(&mut self, reader: &mut impl std::io::Read) -> Result<(), crate::Error> {
-		self.total_previous = self.total;
-		self.total = 0;
-		self.idle_previous = self.idle;
-		self.idle = 0;
-
-		let mut part_num = 0_usize;
-		loop {
-			let part = match crate::Parse::parse(reader) {
-				Ok(part) => part,
-				Err(ref err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
-				Err(err) => return Err(err.into()),
-			};
+		let total = self.total.parse_next(reader)?;
+		if !total {
+			return Err("kern.cp_time is truncated".into());
+		}
 
-			self.total += part;
+		for core_num in 0.. {
+			if core_num == self.cores.len() {
+				self.cores.push(Default::default());
+			}
 
-			if part_num == 4 {
-				self.idle = part;
+			if !self.cores[core_num].parse_next(reader)? {
+				self.cores.truncate(core_num);
+				break;
 			}
+		}
 
-			part_num += 1;
+		Ok(())
+	}
+
+	pub(crate) fn usage_percent(&self) -> Option<f32> {
+		self.total.usage_percent()
+	}
+
+	pub(crate) fn states(&self) -> Option<CpuStates> {
+		self.total.states()
+	}
+
+	pub(crate) fn cores_usage_percent(&self) -> impl ExactSizeIterator<Item = Option<f32>> + '_ {
+		self.cores.iter().map(CpuTimes::usage_percent)
+	}
+
+	pub(crate) fn cores_states(&self) -> impl ExactSizeIterator<Item = Option<CpuStates>> + '_ {
+		self.cores.iter().map(CpuTimes::states)
+	}
+}
+
+/// Percentage of time spent in each CPU state.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CpuStates {
+	pub(crate) user: f32,
+	pub(crate) nice: f32,
+	pub(crate) system: f32,
+	pub(crate) interrupt: f32,
+	pub(crate) idle: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct CpuTimes {
+	previous: [crate::c_ulong; CPUSTATES],
+	current: [crate::c_ulong; CPUSTATES],
+}
+
+impl CpuTimes {
+	// Returns `false` if the reader was already at EOF.
+	fn parse_next(&mut self, reader: &mut impl std::io::Read) -> Result<bool, crate::Error> {
+		self.previous = self.current;
+
+		for (i, value) in self.current.iter_mut().enumerate() {
+			*value = match crate::Parse::parse(reader) {
+				Ok(value) => value,
+				Err(ref err) if err.kind() == std::io::ErrorKind::UnexpectedEof && i == 0 => return Ok(false),
+				Err(err) => return Err(err.into()),
+			};
 		}
+
+		Ok(true)
 	}
 
-	pub(crate) fn usage_percent(self) -> Option<f32> {
-		if self.total_previous > 0 {
-			let cpu_total_diff = self.total - self.total_previous;
-			let cpu_idle_diff = self.idle - self.idle_previous;
-			let cpu_usage_percent = (cpu_total_diff - cpu_idle_diff) as f32 * 100. / cpu_total_diff as f32;
-			Some(cpu_usage_percent)
+	fn diffs(&self) -> Option<([crate::c_ulong; CPUSTATES], crate::c_ulong)> {
+		if self.previous.iter().all(|&value| value == 0) {
+			return None;
 		}
-		else {
-			None
+
+		let mut diffs = [0; CPUSTATES];
+		for (diff, (&current, &previous)) in diffs.iter_mut().zip(self.current.iter().zip(&self.previous)) {
+			*diff = current.saturating_sub(previous);
 		}
+
+		let total_diff = diffs.iter().sum();
+		if total_diff == 0 {
+			return None;
+		}
+
+		Some((diffs, total_diff))
+	}
+
+	fn usage_percent(&self) -> Option<f32> {
+		let (diffs, total_diff) = self.diffs()?;
+		let cpu_usage_percent = (total_diff - diffs[4]) as f32 * 100. / total_diff as f32;
+		Some(cpu_usage_percent)
+	}
+
+	fn states(&self) -> Option<CpuStates> {
+		let (diffs, total_diff) = self.diffs()?;
+		let percent = |diff: crate::c_ulong| diff as f32 * 100. / total_diff as f32;
+		Some(CpuStates {
+			user: percent(diffs[0]),
+			nice: percent(diffs[1]),
+			system: percent(diffs[2]),
+			interrupt: percent(diffs[3]),
+			idle: percent(diffs[4]),
+		})
 	}
 }
@@ -0,0 +1,23 @@
+#[derive(Debug)]
+pub(crate) struct CpuFrequency {
+	// dev.cpu.0.freq doesn't exist on all routers, such as VMs.
+	pub(crate) exists: bool,
+	pub(crate) mhz: crate::c_uint,
+}
+
+impl CpuFrequency {
+	pub(crate) fn get(session: &ssh2::Session) -> Result<Self, crate::Error> {
+		let exists = crate::ssh_exec::sysctl_N::exists("dev.cpu.0.freq", session)?;
+		Ok(CpuFrequency {
+			exists,
+			mhz: 0,
+		})
+	}
+
+	pub(crate) fn update(&mut self, reader: &mut impl std::io::Read) -> Result<(), crate::Error> {
+		if self.exists {
+			self.mhz = crate::Parse::parse(reader)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,171 @@
+// The leases files can be large and only change when a host renews its lease, so they're read less often than the ARP and NDP tables.
+const LEASES_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug)]
+pub(crate) struct Leases {
+	static_mappings: Vec<crate::pfconfig::DhcpStaticMapping>,
+	expired: bool,
+
+	ipv4: Vec<crate::ssh_exec::dhcpd_leases::Lease>,
+	ipv6: Vec<crate::ssh_exec::dhcpd_leases::Lease>,
+
+	/// `None` if the leases files haven't been read yet
+	last_read: Option<std::time::Instant>,
+
+	hosts: Vec<Host>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Host {
+	pub(crate) hostname: Option<String>,
+
+	/// `None` for IPv6 leases of hosts that aren't in the NDP table
+	pub(crate) mac_address: Option<String>,
+
+	/// The device name of the interface that the host is on, if known
+	pub(crate) interface: Option<String>,
+
+	pub(crate) address: Option<String>,
+	pub(crate) ipv6_addresses: Vec<String>,
+	pub(crate) state: State,
+
+	/// Whether the host is in the ARP or NDP table
+	pub(crate) is_online: bool,
+}
+
+#[derive(Debug)]
+pub(crate) enum State {
+	Static,
+
+	/// `expires_in` is `None` if the lease never expires
+	Active { expires_in: Option<std::time::Duration> },
+
+	/// The binding state of a lease that isn't active, like `expired` or `free`
+	Inactive(String),
+}
+
+impl Leases {
+	pub(crate) fn new(static_mappings: Vec<crate::pfconfig::DhcpStaticMapping>, config: &crate::config::DhcpLeases) -> Self {
+		Leases {
+			static_mappings,
+			expired: config.expired,
+
+			ipv4: vec![],
+			ipv6: vec![],
+			last_read: None,
+
+			hosts: vec![],
+		}
+	}
+
+	/// The hosts with static mappings or leases, joined by MAC address, sorted by IPv4 address.
+	pub(crate) fn hosts(&self) -> &[Host] {
+		&self.hosts
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		if self.last_read.is_none_or(|last_read| last_read.elapsed() >= LEASES_REFRESH_INTERVAL) {
+			self.ipv4 = crate::ssh_exec::dhcpd_leases::get_leases("/var/dhcpd/var/db/dhcpd.leases", session)?;
+			self.ipv6 = crate::ssh_exec::dhcpd_leases::get_leases("/var/dhcpd/var/db/dhcpd6.leases", session)?;
+			self.last_read = Some(std::time::Instant::now());
+		}
+		let time_since_leases_read = self.last_read.map(|last_read| last_read.elapsed()).unwrap_or_default();
+
+		let arp_entries = crate::ssh_exec::arp_an::get_entries(session)?;
+		let ndp_entries = crate::ssh_exec::ndp_an::get_entries(session)?;
+
+		// MAC address -> interface, for hosts that are in the ARP or NDP table
+		let online: std::collections::BTreeMap<String, String> =
+			arp_entries.iter().filter_map(|entry| Some((entry.mac_address.as_ref()?.to_lowercase(), entry.interface.clone())))
+			.chain(ndp_entries.iter().filter_map(|entry| Some((entry.mac_address.as_ref()?.to_lowercase(), entry.interface.clone()))))
+			.collect();
+
+		let mut result: Vec<Host> =
+			self.static_mappings.iter()
+			.map(|mapping| Host {
+				hostname: mapping.hostname.clone(),
+				mac_address: Some(mapping.mac_address.clone()),
+				interface: Some(mapping.interface.clone()),
+				address: mapping.address.clone(),
+				ipv6_addresses: vec![],
+				state: State::Static,
+				is_online: false,
+			})
+			.collect();
+
+		for (is_ipv6, lease) in self.ipv4.iter().map(|lease| (false, lease)).chain(self.ipv6.iter().map(|lease| (true, lease))) {
+			let is_active = lease.binding_state == "active";
+			if !is_active && !self.expired {
+				continue;
+			}
+
+			// DHCPv6 leases don't have a MAC address, so use the one that the host is using for that address, if any.
+			let mac_address =
+				if is_ipv6 {
+					ndp_entries.iter()
+					.find(|entry| entry.ip_address == lease.address)
+					.and_then(|entry| entry.mac_address.as_ref())
+					.map(|mac_address| mac_address.to_lowercase())
+				}
+				else {
+					lease.mac_address.clone()
+				};
+
+			let host_index =
+				if let Some(host_index) = result.iter().position(|host| mac_address.is_some() && host.mac_address == mac_address) {
+					host_index
+				}
+				else {
+					result.push(Host {
+						hostname: None,
+						mac_address,
+						interface: None,
+						address: None,
+						ipv6_addresses: vec![],
+						state: State::Inactive(lease.binding_state.clone()),
+						is_online: false,
+					});
+					result.len() - 1
+				};
+			let host = &mut result[host_index];
+
+			if host.hostname.is_none() {
+				host.hostname.clone_from(&lease.hostname);
+			}
+
+			if is_ipv6 {
+				host.ipv6_addresses.push(lease.address.clone());
+			}
+			else if host.address.is_none() || (is_active && !matches!(host.state, State::Static)) {
+				host.address = Some(lease.address.clone());
+			}
+
+			// A host with an active lease is shown as active even if it also has older expired leases.
+			//
+			// The lease's expiry is relative to when the leases file was read, which might have been a while ago.
+			match host.state {
+				State::Static | State::Active { .. } => (),
+				State::Inactive(_) if is_active => host.state = State::Active {
+					expires_in: lease.expires_in.map(|expires_in| expires_in.saturating_sub(time_since_leases_read)),
+				},
+				State::Inactive(_) => host.state = State::Inactive(lease.binding_state.clone()),
+			}
+		}
+
+		for host in &mut result {
+			if let Some(interface) = host.mac_address.as_ref().and_then(|mac_address| online.get(mac_address)) {
+				host.is_online = true;
+				host.interface = Some(interface.clone());
+			}
+		}
+
+		result.sort_by_cached_key(|host| {
+			let address: Option<std::net::Ipv4Addr> = host.address.as_ref().and_then(|address| address.parse().ok());
+			(address.is_none(), address, host.hostname.clone())
+		});
+
+		self.hosts = result;
+
+		Ok(())
+	}
+}
@@ -2,18 +2,51 @@
 pub(crate) struct Disk {
 	pub(crate) name: String,
 	pub(crate) serial_number: String,
-	smart_status_exec: crate::ssh_exec::smartctl_a::Exec,
-	pub(crate) smart_passed: bool,
+
+	// `None` if smartctl is not installed or the device doesn't support SMART
+	smart_status_exec: Option<crate::ssh_exec::smartctl_a::Exec>,
+
+	// `None` if the SMART status is unavailable
+	pub(crate) smart_passed: Option<bool>,
+
 	pub(crate) temperature: crate::c_uint,
+	pub(crate) power_on_hours: Option<u64>,
+
+	// Estimated percentage of the device's endurance that has been used up
+	pub(crate) percentage_used: Option<u8>,
+
+	// Available spare capacity and the threshold below which it's considered critical, as percentages (NVMe only)
+	pub(crate) available_spare: Option<(u8, u8)>,
+
+	pub(crate) error_counters: Vec<ErrorCounter>,
+}
+
+/// A SMART counter that should ideally stay at zero, like the number of reallocated sectors.
+#[derive(Debug)]
+pub(crate) struct ErrorCounter {
+	pub(crate) name: &'static str,
+	pub(crate) value: u64,
+
+	// The value when the dashboard started
+	initial: u64,
+}
+
+impl ErrorCounter {
+	/// How much the counter has increased since the dashboard started.
+	pub(crate) fn increase(&self) -> u64 {
+		self.value.saturating_sub(self.initial)
+	}
 }
 
 impl Disk {
-	pub(crate) fn get_all(session: &ssh2::Session) -> Result<Box<[Self]>, crate::Error> {
+	pub(crate) fn get_all(config: Option<crate::config::Disks>, session: &ssh2::Session) -> Result<Box<[Self]>, crate::Error> {
+		let exclude = config.map(|config| config.exclude).unwrap_or_default();
+
 		let disk_names = crate::ssh_exec::sysctl_kern_disks::run(session)?;
 		let result: Result<Box<[_]>, crate::Error> =
 			disk_names.split(' ')
 			.filter_map(|disk_name| {
-				if disk_name.is_empty() {
+				if disk_name.is_empty() || exclude.iter().any(|excluded| excluded == disk_name) {
 					return None;
 				}
 
@@ -27,26 +60,82 @@ impl Disk {
 	}
 
 	fn new(name: String, session: &ssh2::Session) -> Result<Self, crate::Error> {
-		let serial_number = crate::ssh_exec::smartctl_i::get_serial_number(&name, session)?;
+		let (serial_number, smart_status_exec) = match crate::ssh_exec::smartctl_i::get_info(&name, session)? {
+			Some(crate::ssh_exec::smartctl_i::Info { serial_number, smart_available }) => {
+				let smart_status_exec = smart_available.then(|| crate::ssh_exec::smartctl_a::Exec::new(&name));
+				(serial_number.unwrap_or_default(), smart_status_exec)
+			},
 
-		let smart_status_exec = crate::ssh_exec::smartctl_a::Exec::new(&name);
+			None => (String::new(), None),
+		};
 
 		Ok(Disk {
 			name,
 			serial_number,
 			smart_status_exec,
-			smart_passed: false,
+			smart_passed: None,
 			temperature: 0,
+			power_on_hours: None,
+			percentage_used: None,
+			available_spare: None,
+			error_counters: vec![],
 		})
 	}
 }
 
 impl Disk {
+	pub(crate) fn smart_available(&self) -> bool {
+		self.smart_status_exec.is_some()
+	}
+
 	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
-		let (passed, current) = self.smart_status_exec.run(session)?;
-		self.smart_passed = passed;
-		self.temperature = current;
+		let Some(smart_status_exec) = &self.smart_status_exec else {
+			return Ok(());
+		};
+
+		let output = smart_status_exec.run(session)?;
+		self.smart_passed = output.smart_status.map(|smart_status| smart_status.passed);
+		self.temperature = output.temperature.map_or(0, |temperature| temperature.current);
+		self.power_on_hours = output.power_on_time.map(|power_on_time| power_on_time.hours);
+
+		let mut error_counters = vec![];
+
+		self.percentage_used = None;
+		self.available_spare = None;
+
+		if let Some(attributes) = output.ata_smart_attributes {
+			for attribute in attributes.table {
+				match attribute.id {
+					5 => error_counters.push(("reallocated", attribute.raw.value)),
+					197 => error_counters.push(("pending", attribute.raw.value)),
+					198 => error_counters.push(("uncorrectable", attribute.raw.value)),
+
+					// Vendor-specific wear indicators, whose normalized values count down from 100 to 0 as the flash wears out.
+					//
+					// 177: Wear_Leveling_Count (Samsung), 202: Percent_Lifetime_Remain (Crucial / Micron),
+					// 231: SSD_Life_Left (Kingston, SandForce), 233: Media_Wearout_Indicator (Intel)
+					177 | 202 | 231 | 233 if self.percentage_used.is_none() =>
+						self.percentage_used = Some(100_u8.saturating_sub(attribute.value)),
+
+					_ => (),
+				}
+			}
+		}
+
+		if let Some(log) = output.nvme_smart_health_information_log {
+			self.percentage_used = Some(log.percentage_used);
+			self.available_spare = Some((log.available_spare, log.available_spare_threshold));
+			error_counters.push(("media errors", log.media_errors));
+		}
+
+		self.error_counters = error_counters.into_iter().map(|(name, value)| {
+			let initial =
+				self.error_counters.iter()
+				.find(|counter| counter.name == name)
+				.map_or(value, |counter| counter.initial);
+			ErrorCounter { name, value, initial }
+		}).collect();
+
 		Ok(())
 	}
 }
-
@@ -0,0 +1,114 @@
+#[derive(Debug)]
+pub(crate) struct Filesystems {
+	df: crate::ssh_exec::df::Exec,
+	include: Vec<String>,
+	exclude: Vec<String>,
+	thresholds: std::collections::BTreeMap<String, crate::config::UsageThresholds>,
+}
+
+impl Filesystems {
+	pub(crate) fn new(config: crate::config::Filesystems) -> Self {
+		Filesystems {
+			df: crate::ssh_exec::df::Exec::new(&config.types),
+			include: config.include,
+			exclude: config.exclude,
+			thresholds: config.thresholds,
+		}
+	}
+
+	/// Returns the filesystems whose mountpoints match the `include` globs (if any) and don't match the `exclude` globs.
+	pub(crate) fn get(&self, session: &ssh2::Session) -> Result<Vec<crate::ssh_exec::df::Filesystem>, crate::Error> {
+		let mut filesystems = self.df.run(session)?;
+		filesystems.retain(|filesystem| {
+			let mounted_on = &filesystem.mounted_on;
+			(self.include.is_empty() || self.include.iter().any(|pattern| glob_matches(pattern, mounted_on))) &&
+				!self.exclude.iter().any(|pattern| glob_matches(pattern, mounted_on))
+		});
+		Ok(filesystems)
+	}
+
+	/// Returns the thresholds of the most specific glob in `thresholds` that matches the given mountpoint, if any.
+	///
+	/// A glob is more specific than another if it has more characters that aren't wildcards, so `/var/*` wins over `/*` for `/var/log`.
+	pub(crate) fn thresholds(&self, mounted_on: &str) -> Option<crate::config::UsageThresholds> {
+		self.thresholds.iter()
+			.filter(|(pattern, _)| glob_matches(pattern, mounted_on))
+			.max_by_key(|(pattern, _)| pattern.chars().filter(|&c| c != '*' && c != '?').count())
+			.map(|(_, &thresholds)| thresholds)
+	}
+}
+
+/// Matches `s` against a glob where `*` matches any sequence of characters (including `/`) and `?` matches any one character.
+fn glob_matches(pattern: &str, s: &str) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let s: Vec<char> = s.chars().collect();
+
+	let (mut p, mut i) = (0, 0);
+
+	// The position after the most recent `*` in the pattern, and the position in `s` that it's currently matched up to.
+	let mut backtrack = None;
+
+	while i < s.len() {
+		match pattern.get(p) {
+			Some('*') => {
+				p += 1;
+				backtrack = Some((p, i));
+			},
+
+			Some(&c) if c == '?' || c == s[i] => {
+				p += 1;
+				i += 1;
+			},
+
+			_ => match backtrack {
+				Some((star_p, star_i)) => {
+					p = star_p;
+					i = star_i + 1;
+					backtrack = Some((star_p, i));
+				},
+
+				None => return false,
+			},
+		}
+	}
+
+	pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn glob_matches() {
+		assert!(super::glob_matches("/var/*", "/var/log"));
+		assert!(super::glob_matches("/*", "/var/log"));
+		assert!(super::glob_matches("/var/*/run", "/var/dhcpd/var/run"));
+		assert!(super::glob_matches("/tm?", "/tmp"));
+		assert!(super::glob_matches("/", "/"));
+		assert!(super::glob_matches("*", ""));
+
+		assert!(!super::glob_matches("/var/*", "/var"));
+		assert!(!super::glob_matches("/tm?", "/tmp/foo"));
+		assert!(!super::glob_matches("/", "/tmp"));
+		assert!(!super::glob_matches("/var/*/run", "/var/dhcpd/var/db"));
+	}
+
+	#[test]
+	fn most_specific_thresholds() {
+		let thresholds = |warning| crate::config::UsageThresholds { warning, critical: 95. };
+		let filesystems = super::Filesystems::new(crate::config::Filesystems {
+			thresholds: vec![
+				("/*".to_owned(), thresholds(75.)),
+				("/var/*".to_owned(), thresholds(50.)),
+				("/var/db".to_owned(), thresholds(25.)),
+			].into_iter().collect(),
+			..Default::default()
+		});
+
+		let warning = |mounted_on| filesystems.thresholds(mounted_on).map(|thresholds| thresholds.warning);
+		assert_eq!(warning("/tmp"), Some(75.));
+		assert_eq!(warning("/var/log"), Some(50.));
+		assert_eq!(warning("/var/db"), Some(25.));
+		assert_eq!(warning("/var"), Some(75.));
+		assert_eq!(warning("tmp"), None);
+	}
+}
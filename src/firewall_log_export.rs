@@ -0,0 +1,382 @@
+// How long to wait for the syslog collector to accept a connection or a message, since forwarding blocks the firewall log reader.
+const FORWARD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+// How long to wait before trying to connect to the syslog collector again after failing to.
+const FORWARD_RECONNECT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[derive(Debug)]
+pub(crate) struct Exporter {
+	archive: Option<Archive>,
+
+	/// The error from the most recent write to the archive, if it failed
+	archive_error: Option<String>,
+
+	forwarder: Option<Forwarder>,
+}
+
+impl Exporter {
+	pub(crate) fn new(config: &crate::config::FirewallLogs, ssh: &crate::config::Ssh) -> Result<Self, crate::Error> {
+		let archive = config.archive.as_ref().map(Archive::new).transpose()?;
+
+		let forwarder = config.forward.as_ref().map(|forward| Forwarder::new(forward, ssh));
+
+		Ok(Exporter {
+			archive,
+			archive_error: None,
+			forwarder,
+		})
+	}
+
+	pub(crate) fn archive_error(&self) -> Option<&str> {
+		self.archive_error.as_deref()
+	}
+
+	pub(crate) fn export(&mut self, log: &crate::firewall_logs::Log) -> Result<(), crate::Error> {
+		if self.archive.is_none() && self.forwarder.is_none() {
+			return Ok(());
+		}
+
+		let record = Record::new(log, std::time::SystemTime::now())?;
+
+		// Archiving is best-effort too. A full disk or a failed rotation is shown in the firewall logs section,
+		// and writing is attempted again for the next log.
+		if let Some(archive) = &mut self.archive {
+			self.archive_error =
+				archive.write(&record).err()
+				.map(|err| format!("could not write to firewall log archive {}: {}", archive.path.display(), err.0));
+		}
+
+		if let Some(forwarder) = &mut self.forwarder {
+			forwarder.send(&record);
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Debug, serde_derive::Serialize)]
+struct Record<'a> {
+	received_at: String,
+	timestamp: &'a str,
+	interface: &'a str,
+	action: &'static str,
+	protocol: &'static str,
+	source: std::net::IpAddr,
+	source_port: Option<u16>,
+	destination: std::net::IpAddr,
+	destination_port: Option<u16>,
+}
+
+impl<'a> Record<'a> {
+	const CSV_HEADER: &'static str = "received_at,timestamp,interface,action,protocol,source,source_port,destination,destination_port";
+
+	fn new(log: &'a crate::firewall_logs::Log, received_at: std::time::SystemTime) -> Result<Self, crate::Error> {
+		let action = match log.action {
+			crate::firewall_logs::Action::Block => "block",
+			crate::firewall_logs::Action::Pass => "pass",
+		};
+
+		let (protocol, source, source_port, destination, destination_port) = match log.protocol {
+			crate::firewall_logs::Protocol::Icmp { source, destination } =>
+				("icmp", source, None, destination, None),
+			crate::firewall_logs::Protocol::Tcp { source, destination } =>
+				("tcp", source.ip(), Some(source.port()), destination.ip(), Some(destination.port())),
+			crate::firewall_logs::Protocol::Udp { source, destination } =>
+				("udp", source.ip(), Some(source.port()), destination.ip(), Some(destination.port())),
+		};
+
+		Ok(Record {
+			received_at: rfc3339(received_at)?,
+			timestamp: &log.timestamp,
+			interface: &log.interface,
+			action,
+			protocol,
+			source,
+			source_port,
+			destination,
+			destination_port,
+		})
+	}
+
+	fn to_csv(&self) -> String {
+		format!(
+			"{},{},{},{},{},{},{},{},{}",
+			csv_field(&self.received_at),
+			csv_field(self.timestamp),
+			csv_field(self.interface),
+			self.action,
+			self.protocol,
+			self.source,
+			self.source_port.map(|port| port.to_string()).unwrap_or_default(),
+			self.destination,
+			self.destination_port.map(|port| port.to_string()).unwrap_or_default(),
+		)
+	}
+
+	/// Formats the record as an RFC 5424 syslog message.
+	fn to_syslog(&self, hostname: &str) -> String {
+		// Facility local0 (16), severity informational (6), same as pfSense's own filterlog messages.
+		const PRI: u8 = 16 * 8 + 6;
+
+		// 32473 is the private enterprise number reserved for documentation by RFC 5612.
+		let mut parameters = vec![
+			format!(r#"interface="{}""#, sd_param_value(self.interface)),
+			format!(r#"action="{}""#, self.action),
+			format!(r#"protocol="{}""#, self.protocol),
+			format!(r#"src="{}""#, self.source),
+		];
+		if let Some(source_port) = self.source_port {
+			parameters.push(format!(r#"srcport="{}""#, source_port));
+		}
+		parameters.push(format!(r#"dst="{}""#, self.destination));
+		if let Some(destination_port) = self.destination_port {
+			parameters.push(format!(r#"dstport="{}""#, destination_port));
+		}
+		let structured_data = format!("[filterlog@32473 {}]", parameters.join(" "));
+
+		let message = match self.destination_port {
+			Some(destination_port) => format!("{} {} {}/{} <- {}", self.interface, self.action, destination_port, self.protocol, self.source),
+			None => format!("{} {} {} <- {}", self.interface, self.action, self.protocol, self.source),
+		};
+
+		format!("<{}>1 {} {} filterlog - - {} {}", PRI, self.received_at, hostname, structured_data, message)
+	}
+}
+
+#[derive(Debug)]
+struct Archive {
+	path: std::path::PathBuf,
+	format: crate::config::ArchiveFormat,
+	max_size: Option<u64>,
+	max_files: usize,
+
+	file: std::fs::File,
+	size: u64,
+}
+
+impl Archive {
+	fn new(config: &crate::config::Archive) -> Result<Self, crate::Error> {
+		let (file, size) = open_archive_file(&config.path, config.format)?;
+
+		Ok(Archive {
+			path: config.path.clone(),
+			format: config.format,
+			max_size: config.max_size,
+			max_files: config.max_files,
+
+			file,
+			size,
+		})
+	}
+
+	fn write(&mut self, record: &Record<'_>) -> Result<(), crate::Error> {
+		let mut line = match self.format {
+			crate::config::ArchiveFormat::Csv => record.to_csv(),
+			crate::config::ArchiveFormat::Json => serde_json::to_string(record)?,
+		};
+		line.push('\n');
+
+		if let Some(max_size) = self.max_size {
+			if self.size > 0 && self.size + line.len() as u64 > max_size {
+				self.rotate()?;
+			}
+		}
+
+		std::io::Write::write_all(&mut self.file, line.as_bytes())?;
+		self.size += line.len() as u64;
+
+		Ok(())
+	}
+
+	// Renames path.N-1 to path.N, ..., path to path.1, and then starts a new file at path.
+	fn rotate(&mut self) -> Result<(), crate::Error> {
+		let rotated_path = |i: usize| {
+			let mut path = self.path.clone().into_os_string();
+			path.push(format!(".{}", i));
+			std::path::PathBuf::from(path)
+		};
+
+		if self.max_files == 0 {
+			std::fs::remove_file(&self.path)?;
+		}
+		else {
+			for i in (1..self.max_files).rev() {
+				match std::fs::rename(rotated_path(i), rotated_path(i + 1)) {
+					Ok(()) => (),
+					Err(ref err) if err.kind() == std::io::ErrorKind::NotFound => (),
+					Err(err) => return Err(err.into()),
+				}
+			}
+
+			std::fs::rename(&self.path, rotated_path(1))?;
+		}
+
+		let (file, size) = open_archive_file(&self.path, self.format)?;
+		self.file = file;
+		self.size = size;
+
+		Ok(())
+	}
+}
+
+fn open_archive_file(path: &std::path::Path, format: crate::config::ArchiveFormat) -> Result<(std::fs::File, u64), crate::Error> {
+	let mut file =
+		std::fs::OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(path)
+		.map_err(|err| format!("could not open firewall log archive {}: {}", path.display(), err))?;
+	let mut size = file.metadata()?.len();
+
+	if size == 0 {
+		if let crate::config::ArchiveFormat::Csv = format {
+			let header = format!("{}\n", Record::CSV_HEADER);
+			std::io::Write::write_all(&mut file, header.as_bytes())?;
+			size = header.len() as u64;
+		}
+	}
+
+	Ok((file, size))
+}
+
+#[derive(Debug)]
+struct Forwarder {
+	address: String,
+	protocol: crate::config::SyslogProtocol,
+	hostname: String,
+
+	udp_socket: Option<std::net::UdpSocket>,
+	tcp_stream: Option<std::net::TcpStream>,
+	last_connect_attempt: Option<std::time::Instant>,
+}
+
+impl Forwarder {
+	fn new(config: &crate::config::Forward, ssh: &crate::config::Ssh) -> Self {
+		// The ssh hostname is of the form `host:port` or `[host]:port`
+		let hostname = ssh.hostname.rsplitn(2, ':').last().unwrap_or(&ssh.hostname);
+		let hostname = hostname.trim_start_matches('[').trim_end_matches(']');
+
+		Forwarder {
+			address: config.address.clone(),
+			protocol: config.protocol,
+			hostname: hostname.to_owned(),
+
+			udp_socket: None,
+			tcp_stream: None,
+			last_connect_attempt: None,
+		}
+	}
+
+	// Forwarding is best-effort. A collector that is down or unreachable should not take the dashboard down with it,
+	// so errors just cause the connection to be re-established for the next message.
+	fn send(&mut self, record: &Record<'_>) {
+		let message = record.to_syslog(&self.hostname);
+
+		match self.protocol {
+			crate::config::SyslogProtocol::Udp => {
+				if self.udp_socket.is_none() {
+					self.udp_socket = connect_udp(&self.address).ok();
+				}
+
+				if let Some(udp_socket) = &self.udp_socket {
+					if udp_socket.send(message.as_bytes()).is_err() {
+						self.udp_socket = None;
+					}
+				}
+			},
+
+			crate::config::SyslogProtocol::Tcp => {
+				let is_reconnect_due =
+					self.last_connect_attempt.is_none_or(|last_connect_attempt| last_connect_attempt.elapsed() >= FORWARD_RECONNECT_INTERVAL);
+				if self.tcp_stream.is_none() && is_reconnect_due {
+					self.last_connect_attempt = Some(std::time::Instant::now());
+					self.tcp_stream = connect_tcp(&self.address).ok();
+				}
+
+				if let Some(tcp_stream) = &mut self.tcp_stream {
+					// RFC 6587 octet-counting framing
+					let frame = format!("{} {}", message.len(), message);
+					if std::io::Write::write_all(tcp_stream, frame.as_bytes()).is_err() {
+						self.tcp_stream = None;
+					}
+				}
+			},
+		}
+	}
+}
+
+fn connect_tcp(address: &str) -> std::io::Result<std::net::TcpStream> {
+	let address =
+		std::net::ToSocketAddrs::to_socket_addrs(address)?
+		.next()
+		.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "syslog collector address did not resolve"))?;
+
+	let tcp_stream = std::net::TcpStream::connect_timeout(&address, FORWARD_TIMEOUT)?;
+	tcp_stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+	Ok(tcp_stream)
+}
+
+fn connect_udp(address: &str) -> std::io::Result<std::net::UdpSocket> {
+	let address =
+		std::net::ToSocketAddrs::to_socket_addrs(address)?
+		.next()
+		.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "syslog collector address did not resolve"))?;
+
+	let bind_address: std::net::SocketAddr = match address {
+		std::net::SocketAddr::V4(_) => (std::net::Ipv4Addr::UNSPECIFIED, 0).into(),
+		std::net::SocketAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into(),
+	};
+	let udp_socket = std::net::UdpSocket::bind(bind_address)?;
+	udp_socket.connect(address)?;
+	Ok(udp_socket)
+}
+
+/// Quotes a CSV field if it contains a separator, quote or newline. Ref: RFC 4180
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+	if value.contains([',', '"', '\r', '\n']) {
+		format!(r#""{}""#, value.replace('"', r#""""#)).into()
+	}
+	else {
+		value.into()
+	}
+}
+
+/// Escapes the characters that are not allowed unescaped in an SD-PARAM value. Ref: RFC 5424 section 6.3.3
+fn sd_param_value(value: &str) -> String {
+	let mut result = String::with_capacity(value.len());
+	for c in value.chars() {
+		if matches!(c, '"' | '\\' | ']') {
+			result.push('\\');
+		}
+		result.push(c);
+	}
+	result
+}
+
+/// Formats the given time as an RFC 3339 timestamp in UTC, with millisecond precision.
+fn rfc3339(time: std::time::SystemTime) -> Result<String, crate::Error> {
+	let since_epoch = time.duration_since(std::time::UNIX_EPOCH)?;
+	let seconds = since_epoch.as_secs();
+
+	// Ref: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+	let days = seconds / 86400 + 719_468;
+	let era = days / 146_097;
+	let day_of_era = days % 146_097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let mp = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+	Ok(format!(
+		"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+		year,
+		month,
+		day,
+		(seconds % 86400) / 3600,
+		(seconds % 3600) / 60,
+		seconds % 60,
+		since_epoch.subsec_millis(),
+	))
+}
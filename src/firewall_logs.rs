@@ -1,42 +1,229 @@
 #[derive(Debug)]
 pub(crate) struct Logs {
-	inner: [Option<Log>; 10],
+	// Newest log first.
+	inner: std::collections::VecDeque<Log>,
+	capacity: usize,
 
-	// Index of the newest log. Moves backwards as new logs are pushed.
-	head: usize,
+	// Number of logs matching the search that are newer than the first displayed log.
+	scroll_offset: usize,
+
+	search: Option<String>,
+	is_editing_search: bool,
+
+	// Lowercased `search`, and the number of logs that match it, kept up to date so that they don't need to be recomputed for every log.
+	search_lowercase: Option<String>,
+	num_matching: usize,
+
+	// Number of logs that were displayed in the previous iteration, used for paging.
+	page_len: usize,
+
+	detector: crate::scan_detection::Detector,
+
+	// The error from the most recent attempt to archive a log, if it failed.
+	archive_error: Option<String>,
 }
 
 impl Logs {
-	pub(crate) fn new(interfaces: impl IntoIterator<Item = String>, ssh: &crate::config::Ssh) -> Result<std::sync::Arc<std::sync::Mutex<Self>>, crate::Error> {
+	pub(crate) fn new(
+		interfaces: impl IntoIterator<Item = String>,
+		ssh: &crate::config::Ssh,
+		config: &crate::config::FirewallLogs,
+		original_mode: Option<crate::tty::OriginalMode>,
+	) -> Result<std::sync::Arc<std::sync::Mutex<Self>>, crate::Error> {
 		let result = std::sync::Arc::new(std::sync::Mutex::new(Logs {
-			inner: Default::default(),
-			head: 0,
+			inner: std::collections::VecDeque::with_capacity(config.capacity),
+			capacity: config.capacity,
+			scroll_offset: 0,
+			search: None,
+			is_editing_search: false,
+			search_lowercase: None,
+			num_matching: 0,
+			page_len: 1,
+			detector: crate::scan_detection::Detector::new(config.detection.as_ref()),
+			archive_error: None,
 		}));
 
-		// Can't multiplex on the same session because ssh2 has internal mutexes to only let one command run at a time.
-		// So create a new connection and session.
-		let session = crate::connect(&ssh.hostname, &ssh.username, None)?;
-
 		let logs = result.clone();
 
 		let interfaces = interfaces.into_iter().collect();
 
-		let _ = std::thread::spawn(move || if let Err(err) = log_reader_thread(&logs, &session, &interfaces) {
-			eprintln!("{:?}", err);
-			std::process::exit(1);
-		});
+		let mut exporter = crate::firewall_log_export::Exporter::new(config, ssh)?;
+
+		if let Some(receive) = &config.receive {
+			let receiver = crate::syslog_receiver::Receiver::bind(receive)?;
+
+			let _ = std::thread::spawn(move || {
+				let result = receiver.run(|line| handle_line(line, &logs, &interfaces, &mut exporter));
+				exit(result, original_mode);
+			});
+		}
+		else {
+			// Can't multiplex on the same session because ssh2 has internal mutexes to only let one command run at a time.
+			// So create a new connection and session.
+			let session = crate::connect(&ssh.hostname, &ssh.username, None)?;
+
+			let _ = std::thread::spawn(move || {
+				let result = log_reader_thread(&logs, &session, &interfaces, &mut exporter);
+				exit(result, original_mode);
+			});
+		}
 
 		Ok(result)
 	}
 
+	pub(crate) fn spawn_key_reader_thread(logs: std::sync::Arc<std::sync::Mutex<Self>>, original_mode: crate::tty::OriginalMode) {
+		let _ = std::thread::spawn(move || {
+			let result = key_reader_thread(&logs);
+			exit(result, Some(original_mode));
+		});
+	}
+
+	/// Iterates over the logs that match the search, newest first, starting from the current scroll position.
 	pub(crate) fn iter(&self) -> impl Iterator<Item = &'_ Log> {
-		let (second, first) = self.inner.split_at(self.head);
-		first.iter().chain(second).flat_map(Option::as_ref)
+		self.iter_matching().skip(self.scroll_offset)
+	}
+
+	pub(crate) fn num_matching(&self) -> usize {
+		self.num_matching
+	}
+
+	pub(crate) fn scroll_offset(&self) -> usize {
+		self.scroll_offset
+	}
+
+	/// Returns the search query, and whether it's still being typed.
+	pub(crate) fn search(&self) -> Option<(&str, bool)> {
+		self.search.as_ref().map(|search| (&**search, self.is_editing_search))
+	}
+
+	pub(crate) fn set_page_len(&mut self, page_len: usize) {
+		self.page_len = page_len.max(1);
+		self.clamp_scroll_offset();
+	}
+
+	pub(crate) fn archive_error(&self) -> Option<&str> {
+		self.archive_error.as_deref()
+	}
+
+	pub(crate) fn alerts(&mut self) -> Vec<&crate::scan_detection::Alert> {
+		self.detector.alerts()
+	}
+
+	fn iter_matching(&self) -> impl Iterator<Item = &'_ Log> {
+		self.inner.iter().filter(move |log| self.matches_search(log))
+	}
+
+	fn matches_search(&self, log: &Log) -> bool {
+		self.search_lowercase.as_ref().is_none_or(|search| log.matches(search))
+	}
+
+	fn search_changed(&mut self) {
+		self.search_lowercase = self.search.as_ref().map(|search| search.to_lowercase());
+		self.num_matching = self.iter_matching().count();
 	}
 
 	fn push(&mut self, log: Log) {
-		self.head = (self.head + self.inner.len() - 1) % self.inner.len();
-		self.inner[self.head] = Some(log);
+		self.detector.observe(&log);
+
+		if self.matches_search(&log) {
+			self.num_matching += 1;
+
+			// Keep the displayed logs in place if the user has scrolled back.
+			if self.scroll_offset > 0 {
+				self.scroll_offset += 1;
+			}
+		}
+
+		self.inner.push_front(log);
+
+		if self.inner.len() > self.capacity {
+			if let Some(log) = self.inner.pop_back() {
+				if self.matches_search(&log) {
+					self.num_matching -= 1;
+				}
+			}
+
+			if self.scroll_offset > 0 {
+				self.clamp_scroll_offset();
+			}
+		}
+	}
+
+	fn clamp_scroll_offset(&mut self) {
+		let max_scroll_offset = self.num_matching().saturating_sub(self.page_len);
+		self.scroll_offset = self.scroll_offset.min(max_scroll_offset);
+	}
+
+	// Returns `false` if the dashboard should exit.
+	fn handle_key(&mut self, key: crate::tty::Key) -> bool {
+		if self.is_editing_search {
+			match key {
+				crate::tty::Key::Char(c) => {
+					self.search.get_or_insert_with(Default::default).push(c);
+					self.search_changed();
+					self.scroll_offset = 0;
+				},
+
+				crate::tty::Key::Backspace => {
+					if let Some(search) = &mut self.search {
+						let _ = search.pop();
+					}
+					self.search_changed();
+					self.scroll_offset = 0;
+				},
+
+				crate::tty::Key::Enter => {
+					self.is_editing_search = false;
+					if self.search.as_ref().is_none_or(String::is_empty) {
+						self.search = None;
+						self.search_changed();
+					}
+				},
+
+				crate::tty::Key::Escape => {
+					self.is_editing_search = false;
+					self.search = None;
+					self.search_changed();
+					self.scroll_offset = 0;
+				},
+
+				crate::tty::Key::Interrupt => return false,
+
+				_ => (),
+			}
+
+			return true;
+		}
+
+		match key {
+			crate::tty::Key::Up | crate::tty::Key::Char('k') => self.scroll_offset += 1,
+			crate::tty::Key::Down | crate::tty::Key::Char('j') => self.scroll_offset = self.scroll_offset.saturating_sub(1),
+			crate::tty::Key::PageUp => self.scroll_offset += self.page_len,
+			crate::tty::Key::PageDown => self.scroll_offset = self.scroll_offset.saturating_sub(self.page_len),
+			crate::tty::Key::Home => self.scroll_offset = usize::MAX,
+			crate::tty::Key::End => self.scroll_offset = 0,
+
+			crate::tty::Key::Char('/') => {
+				self.is_editing_search = true;
+				self.search = Some(String::new());
+				self.search_changed();
+				self.scroll_offset = 0;
+			},
+
+			crate::tty::Key::Escape => {
+				self.search = None;
+				self.search_changed();
+				self.scroll_offset = 0;
+			},
+
+			crate::tty::Key::Char('q') | crate::tty::Key::Interrupt => return false,
+
+			_ => (),
+		}
+
+		self.clamp_scroll_offset();
+
+		true
 	}
 }
 
@@ -126,6 +313,18 @@ impl Log {
 			protocol,
 		})
 	}
+
+	// `search` must already be lowercase.
+	fn matches(&self, search: &str) -> bool {
+		let (protocol, source, destination) = match self.protocol {
+			Protocol::Icmp { source, destination } => ("icmp".to_owned(), source.to_string(), destination.to_string()),
+			Protocol::Tcp { source, destination } => (format!("{}/tcp", destination.port()), source.to_string(), destination.to_string()),
+			Protocol::Udp { source, destination } => (format!("{}/udp", destination.port()), source.to_string(), destination.to_string()),
+		};
+
+		let haystack = format!("{} {} {} {} {} {}", self.timestamp, self.interface, self.action, protocol, source, destination).to_lowercase();
+		haystack.contains(search)
+	}
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -162,20 +361,18 @@ pub(crate) enum Protocol {
 	Udp { source: std::net::SocketAddr, destination: std::net::SocketAddr },
 }
 
-fn log_reader_thread(logs: &std::sync::Mutex<Logs>, session: &ssh2::Session, interfaces: &std::collections::BTreeSet<String>) -> Result<(), crate::Error> {
+fn log_reader_thread(
+	logs: &std::sync::Mutex<Logs>,
+	session: &ssh2::Session,
+	interfaces: &std::collections::BTreeSet<String>,
+	exporter: &mut crate::firewall_log_export::Exporter,
+) -> Result<(), crate::Error> {
 	loop {
 		let lines = crate::ssh_exec::clog_filter_log::run(session);
 
 		for line in lines {
 			let line = line?;
-
-			let log = match Log::from_str(&line, interfaces) {
-				Ok(log) => log,
-				Err(()) => continue,
-			};
-
-			let mut logs = logs.lock().expect("could not lock firewall logs queue");
-			logs.push(log);
+			handle_line(&line, logs, interfaces, exporter)?;
 		}
 
 		// `clog -f` returned, for some reason. Restart it.
@@ -183,6 +380,58 @@ fn log_reader_thread(logs: &std::sync::Mutex<Logs>, session: &ssh2::Session, int
 	}
 }
 
+fn handle_line(
+	line: &str,
+	logs: &std::sync::Mutex<Logs>,
+	interfaces: &std::collections::BTreeSet<String>,
+	exporter: &mut crate::firewall_log_export::Exporter,
+) -> Result<(), crate::Error> {
+	let log = match Log::from_str(line, interfaces) {
+		Ok(log) => log,
+		Err(()) => return Ok(()),
+	};
+
+	exporter.export(&log)?;
+
+	let mut logs = logs.lock().expect("could not lock firewall logs queue");
+	if logs.archive_error.as_deref() != exporter.archive_error() {
+		logs.archive_error = exporter.archive_error().map(ToOwned::to_owned);
+	}
+	logs.push(log);
+
+	Ok(())
+}
+
+/// Exits the process from a background thread.
+///
+/// `RawMode` isn't dropped when the process exits from another thread, so the terminal is restored here instead.
+fn exit(result: Result<(), crate::Error>, original_mode: Option<crate::tty::OriginalMode>) -> ! {
+	if let Some(original_mode) = original_mode {
+		original_mode.restore();
+	}
+
+	match result {
+		Ok(()) => std::process::exit(0),
+		Err(err) => {
+			eprintln!("{:?}", err);
+			std::process::exit(1);
+		},
+	}
+}
+
+fn key_reader_thread(logs: &std::sync::Mutex<Logs>) -> Result<(), crate::Error> {
+	for key in crate::tty::keys() {
+		let key = key?;
+
+		let mut logs = logs.lock().expect("could not lock firewall logs queue");
+		if !logs.handle_key(key) {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
 fn ip_addr_from_parts(ipv4_or_v6: &str, ip: &str) -> Option<std::net::IpAddr> {
 	match ipv4_or_v6 {
 		"4" => Some(std::net::IpAddr::V4(ip.parse().ok()?)),
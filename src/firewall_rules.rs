@@ -0,0 +1,182 @@
+#[derive(Debug)]
+pub(crate) struct FirewallRules {
+	rule_descriptions: std::collections::BTreeMap<u32, String>,
+
+	/// Map of alias name to its description
+	table_aliases: std::collections::BTreeMap<String, Option<String>>,
+
+	max_rules: usize,
+	max_tables: usize,
+
+	/// Sorted by the number of packets that each rule matched since the previous update
+	rules: Vec<Rule>,
+
+	/// Sorted by the number of lookups that matched each table since the previous update
+	tables: Vec<Table>,
+}
+
+/// The counters of all the pf rules that pfSense generated from one of its rules, or of one pf rule that isn't from pfSense's rules
+#[derive(Debug)]
+pub(crate) struct Rule {
+	key: RuleKey,
+	pub(crate) label: String,
+	counters: RuleCounters,
+	counters_previous: Option<RuleCounters>,
+	pub(crate) states: u64,
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum RuleKey {
+	Tracker(u32),
+	Label(String),
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct RuleCounters {
+	evaluations: u64,
+	packets: u64,
+	bytes: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct Table {
+	pub(crate) name: String,
+	pub(crate) description: Option<String>,
+	pub(crate) addresses: u64,
+	matches: u64,
+	matches_previous: Option<u64>,
+	pub(crate) blocked_packets: u64,
+	pub(crate) passed_packets: u64,
+}
+
+impl FirewallRules {
+	pub(crate) fn new(
+		rule_descriptions: std::collections::BTreeMap<u32, String>,
+		table_aliases: impl IntoIterator<Item = crate::pfconfig::TableAlias>,
+		config: &crate::config::FirewallRules,
+	) -> Self {
+		let table_aliases =
+			table_aliases.into_iter()
+			.map(|crate::pfconfig::TableAlias { name, description }| (name, description))
+			.collect();
+
+		FirewallRules {
+			rule_descriptions,
+			table_aliases,
+			max_rules: config.max_rules,
+			max_tables: config.max_tables,
+			rules: vec![],
+			tables: vec![],
+		}
+	}
+
+	pub(crate) fn rules(&self) -> &[Rule] {
+		&self.rules[..self.rules.len().min(self.max_rules)]
+	}
+
+	pub(crate) fn tables(&self) -> &[Table] {
+		&self.tables[..self.tables.len().min(self.max_tables)]
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		let mut rules: std::collections::BTreeMap<RuleKey, Rule> = Default::default();
+
+		for pf_rule in crate::ssh_exec::pfctl_vvs_rules::get_rules(session)? {
+			// pfSense gives all the pf rules that it generates from one of its rules, like the IPv4 and IPv6 variants, the same ridentifier.
+			let key = match (pf_rule.ridentifier, &pf_rule.label) {
+				(Some(tracker), _) => RuleKey::Tracker(tracker),
+				(None, Some(label)) => RuleKey::Label(label.clone()),
+				(None, None) => continue,
+			};
+
+			let rule = rules.entry(key).or_insert_with_key(|key| {
+				let label = match key {
+					RuleKey::Tracker(tracker) =>
+						self.rule_descriptions.get(tracker).cloned()
+						.or_else(|| pf_rule.label.as_deref().map(strip_user_rule_prefix))
+						.unwrap_or_else(|| format!("rule {}", tracker)),
+					RuleKey::Label(label) => strip_user_rule_prefix(label),
+				};
+
+				Rule {
+					key: key.clone(),
+					label,
+					counters: Default::default(),
+					counters_previous: self.rules.iter().find(|rule| rule.key == *key).map(|rule| rule.counters),
+					states: 0,
+				}
+			});
+
+			rule.counters.evaluations += pf_rule.evaluations;
+			rule.counters.packets += pf_rule.packets;
+			rule.counters.bytes += pf_rule.bytes;
+			rule.states += pf_rule.states;
+		}
+
+		self.rules = rules.into_values().collect();
+		self.rules.sort_by_key(|rule| std::cmp::Reverse((rule.recent_packets(), rule.counters.packets)));
+
+		let tables =
+			crate::ssh_exec::pfctl_vvs_tables::get_tables(session)?.into_iter()
+			.filter_map(|table| {
+				// pfBlockerNG's tables are named like `pfB_PRI1_v4`, and are only in config.xml if they're also used as aliases.
+				let description = match self.table_aliases.get(&table.name) {
+					Some(description) => description.clone(),
+					None if table.name.starts_with("pfB_") => None,
+					None => return None,
+				};
+
+				let matches_previous = self.tables.iter().find(|previous| previous.name == table.name).map(|previous| previous.matches);
+
+				Some(Table {
+					name: table.name,
+					description,
+					addresses: table.addresses,
+					matches: table.matches,
+					matches_previous,
+					blocked_packets: table.blocked_packets,
+					passed_packets: table.passed_packets,
+				})
+			})
+			.collect();
+		self.tables = tables;
+		self.tables.sort_by_key(|table| std::cmp::Reverse((table.recent_matches(), table.matches)));
+
+		Ok(())
+	}
+}
+
+impl Rule {
+	/// Evaluations, packets and bits per second
+	pub(crate) fn rates(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32, f32)> {
+		let counters_previous = self.counters_previous?;
+
+		// Counters are reset when the ruleset is reloaded, so don't let that show up as a negative rate.
+		Some((
+			crate::per_second(self.counters.evaluations, counters_previous.evaluations, time_since_previous),
+			crate::per_second(self.counters.packets, counters_previous.packets, time_since_previous),
+			crate::per_second(self.counters.bytes, counters_previous.bytes, time_since_previous) * 8.,
+		))
+	}
+
+	fn recent_packets(&self) -> u64 {
+		self.counters_previous.map_or(0, |counters_previous| self.counters.packets.saturating_sub(counters_previous.packets))
+	}
+}
+
+impl Table {
+	/// Matching lookups per second
+	pub(crate) fn match_rate(&self, time_since_previous: std::time::Duration) -> Option<f32> {
+		let matches_previous = self.matches_previous?;
+		Some(crate::per_second(self.matches, matches_previous, time_since_previous))
+	}
+
+	fn recent_matches(&self) -> u64 {
+		self.matches_previous.map_or(0, |matches_previous| self.matches.saturating_sub(matches_previous))
+	}
+}
+
+/// pfSense labels the rules that it generates from user rules like `USER_RULE: Allow LAN`
+fn strip_user_rule_prefix(label: &str) -> String {
+	label.strip_prefix("USER_RULE: ").unwrap_or(label).to_owned()
+}
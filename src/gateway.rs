@@ -1,33 +1,93 @@
 #[derive(Debug)]
 pub(crate) struct Gateways {
-	gateways: std::collections::BTreeMap<String, Option<Gateway>>,
+	by_name: std::collections::BTreeMap<String, Gateway>,
+	groups: Vec<crate::pfconfig::GatewayGroup>,
+	default_gateway_v4: Option<String>,
+	default_gateway_v6: Option<String>,
 }
 
 impl Gateways {
-	pub(crate) fn new(gateways: impl IntoIterator<Item = crate::pfconfig::Gateway>) -> Self {
-		let gateways =
+	pub(crate) fn new(
+		gateways: impl IntoIterator<Item = crate::pfconfig::Gateway>,
+		groups: Vec<crate::pfconfig::GatewayGroup>,
+		default_gateway_v4: Option<String>,
+		default_gateway_v6: Option<String>,
+	) -> Self {
+		let by_name =
 			gateways.into_iter()
-			.map(|crate::pfconfig::Gateway { name }| (name, None))
+			.map(|config| (config.name.clone(), Gateway { config, statistics: None }))
 			.collect();
 		Gateways {
-			gateways,
+			by_name,
+			groups,
+			default_gateway_v4,
+			default_gateway_v6,
 		}
 	}
 
-	pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, Option<Gateway>)> {
-		self.gateways.iter().map(|(name, gateway)| (&**name, *gateway))
+	pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &Gateway)> {
+		self.by_name.iter().map(|(name, gateway)| (&**name, gateway))
+	}
+
+	/// Returns each gateway group's name, and its members with their tiers and whether they're currently active.
+	///
+	/// Like pfSense, the active members are the ones in the lowest tier that has any members that are up,
+	/// where the group's trigger decides whether members with high latency or loss count as up.
+	pub(crate) fn groups(&self) -> impl Iterator<Item = (&str, Vec<(&str, u32, bool)>)> {
+		self.groups.iter().map(move |group| {
+			let is_up = |gateway_name: &str| match self.by_name.get(gateway_name).map(Gateway::status) {
+				Some(Status::Online) => true,
+				Some(Status::Loss) => !group.trigger.on_loss,
+				Some(Status::Delay) => !group.trigger.on_latency,
+				Some(Status::Down) | None => false,
+			};
+
+			let active_tier =
+				group.members.iter()
+				.filter(|(gateway_name, _)| is_up(gateway_name))
+				.map(|&(_, tier)| tier)
+				.min();
+
+			let mut members: Vec<_> =
+				group.members.iter()
+				.map(|(gateway_name, tier)| (&**gateway_name, *tier, Some(*tier) == active_tier && is_up(gateway_name)))
+				.collect();
+			members.sort_by_key(|&(_, tier, _)| tier);
+
+			(&*group.name, members)
+		})
+	}
+
+	/// Returns the gateway that the default route is expected to go through, if config.xml specifies one.
+	///
+	/// This is the configured default gateway, or the highest-tier member of the configured default gateway group.
+	pub(crate) fn preferred_default_gateway(&self, is_ipv6: bool) -> Option<(&str, &Gateway)> {
+		let default_gateway = if is_ipv6 { self.default_gateway_v6.as_deref() } else { self.default_gateway_v4.as_deref() }?;
+
+		let gateway_name =
+			match self.groups.iter().find(|group| group.name == default_gateway) {
+				Some(group) =>
+					group.members.iter()
+					.filter(|(gateway_name, _)| self.by_name.contains_key(gateway_name))
+					.min_by_key(|&&(_, tier)| tier)
+					.map(|(gateway_name, _)| &**gateway_name)?,
+				None => default_gateway,
+			};
+
+		let (gateway_name, gateway) = self.by_name.get_key_value(gateway_name)?;
+		Some((gateway_name, gateway))
 	}
 
 	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
-		for gateway in self.gateways.values_mut() {
-			*gateway = None;
+		for gateway in self.by_name.values_mut() {
+			gateway.statistics = None;
 		}
 
 		let gateway_pinger_statistics = crate::ssh_exec::dpinger::get_statistics(session);
 		for gateway_pinger_statistics in gateway_pinger_statistics {
 			let crate::ssh_exec::dpinger::Statistics { name, latency_average, latency_stddev, ping_packet_loss } = gateway_pinger_statistics?;
-			if let Some(gateway) = self.gateways.get_mut(&name) {
-				*gateway = Some(Gateway {
+			if let Some(gateway) = self.by_name.get_mut(&name) {
+				gateway.statistics = Some(Statistics {
 					latency_average,
 					latency_stddev,
 					ping_packet_loss,
@@ -39,9 +99,78 @@ impl Gateways {
 	}
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct Gateway {
+	config: crate::pfconfig::Gateway,
+
+	/// `None` if dpinger is not running for this gateway
+	pub(crate) statistics: Option<Statistics>,
+}
+
+impl Gateway {
+	pub(crate) fn monitor(&self) -> Option<&str> {
+		self.config.monitor.as_deref()
+	}
+
+	/// Whether the given route goes through this gateway.
+	pub(crate) fn is_route_via(&self, route: &crate::ssh_exec::netstat_rn::Route) -> bool {
+		if route.is_ipv6 != self.config.is_ipv6 || route.interface != self.config.interface {
+			return false;
+		}
+
+		// Dynamic gateways can only be matched by interface. Link-local gateways are printed with a scope, like `fe80::1%em0`
+		self.config.address.as_ref().is_none_or(|address| route.gateway.split('%').next() == Some(&**address))
+	}
+
+	pub(crate) fn is_monitored(&self) -> bool {
+		!self.config.monitor_disabled
+	}
+
+	/// Classifies the gateway the same way as pfSense's `return_gateways_status`
+	pub(crate) fn status(&self) -> Status {
+		if self.config.monitor_disabled {
+			return Status::Online;
+		}
+
+		let Some(Statistics { latency_average, ping_packet_loss, .. }) = self.statistics else {
+			return Status::Down;
+		};
+
+		#[allow(clippy::useless_conversion)] // c_ulong -> u64 is not necessarily identity conversion
+		let ping_packet_loss: u64 = ping_packet_loss.into();
+
+		if latency_average > self.config.latency_high || ping_packet_loss > self.config.loss_high {
+			Status::Down
+		}
+		else if latency_average > self.config.latency_low {
+			Status::Delay
+		}
+		else if ping_packet_loss > self.config.loss_low {
+			Status::Loss
+		}
+		else {
+			Status::Online
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Statistics {
 	pub(crate) latency_average: std::time::Duration,
 	pub(crate) latency_stddev: std::time::Duration,
 	pub(crate) ping_packet_loss: crate::c_ulong,
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Status {
+	Online,
+
+	/// Latency is above the low threshold
+	Delay,
+
+	/// Packet loss is above the low threshold
+	Loss,
+
+	/// Latency or packet loss is above the high threshold, or dpinger is not running
+	Down,
+}
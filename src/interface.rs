@@ -1,68 +1,125 @@
 #[derive(Debug)]
 pub(crate) struct Interfaces {
-	gateways: std::collections::BTreeMap<String, Interface>,
-	bridges: std::collections::BTreeMap<String, Interface>,
-	other: std::collections::BTreeMap<String, Interface>,
+	/// In display order, ie gateway interfaces, then bridges, then the rest, except that each VLAN comes right after its parent.
+	///
+	/// The `bool`s are whether the interface is a bridge, and whether it's a VLAN that's nested under its parent.
+	inner: Vec<(String, Interface, bool, bool)>,
 }
 
 impl Interfaces {
-	pub(crate) fn new(gateways: impl IntoIterator<Item = String>, bridges: impl IntoIterator<Item = String>, other: impl IntoIterator<Item = String>) -> Self {
-		let make_pair = |name: String| { let interface = Interface::new(&name); (name, interface) };
+	pub(crate) fn new(
+		config: Option<crate::config::Interfaces>,
+		topologies: &std::collections::BTreeMap<String, crate::pfconfig::Topology>,
+		gateways: impl IntoIterator<Item = String>,
+		bridges: impl IntoIterator<Item = String>,
+		other: impl IntoIterator<Item = String>,
+	) -> Self {
+		let config = config.unwrap_or_default();
+		let make_pair = |name: String| {
+			let minimum_speed_mbps = config.minimum_speeds.get(&name).copied().unwrap_or(config.minimum_speed);
+			let interface = Interface::new(&name, topologies.get(&name).cloned(), minimum_speed_mbps);
+			(name, interface)
+		};
+		let gateways: std::collections::BTreeMap<_, _> = gateways.into_iter().map(make_pair).collect();
+		let bridges: std::collections::BTreeMap<_, _> = bridges.into_iter().map(make_pair).collect();
+		let other: std::collections::BTreeMap<_, _> = other.into_iter().map(make_pair).collect();
+
+		let all: Vec<_> =
+			gateways.into_iter().map(|(name, interface)| (name, interface, false))
+			.chain(bridges.into_iter().map(|(name, interface)| (name, interface, true)))
+			.chain(other.into_iter().map(|(name, interface)| (name, interface, false)))
+			.collect();
+
+		// VLANs whose parent is also shown are moved to after their parent, in the same order amongst themselves.
+		let vlan_parent = |interface: &Interface| match &interface.topology {
+			Some(crate::pfconfig::Topology::Vlan { parent, .. }) if all.iter().any(|(name, ..)| name == parent) => Some(parent.clone()),
+			_ => None,
+		};
+		let vlan_parents: Vec<_> = all.iter().map(|(_, interface, _)| vlan_parent(interface)).collect();
+		let (mut nested, top_level): (Vec<_>, Vec<_>) =
+			all.into_iter().zip(vlan_parents)
+			.partition(|(_, vlan_parent)| vlan_parent.is_some());
+
+		let mut inner = vec![];
+		for ((name, interface, is_bridge), _) in top_level {
+			let children: Vec<_> = {
+				let (children, rest) = nested.into_iter().partition(|(_, vlan_parent)| vlan_parent.as_ref() == Some(&name));
+				nested = rest;
+				children
+			};
+			inner.push((name, interface, is_bridge, false));
+			inner.extend(children.into_iter().map(|((name, interface, is_bridge), _)| (name, interface, is_bridge, true)));
+		}
+
 		Interfaces {
-			gateways: gateways.into_iter().map(make_pair).collect(),
-			bridges: bridges.into_iter().map(make_pair).collect(),
-			other: other.into_iter().map(make_pair).collect(),
+			inner,
 		}
 	}
 
-	pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&'_ str, &'_ mut Interface, bool)> {
-		self.gateways.iter_mut().map(|(name, interface)| (name.as_ref(), interface, false))
-		.chain(self.bridges.iter_mut().map(|(name, interface)| (name.as_ref(), interface, true)))
-		.chain(self.other.iter_mut().map(|(name, interface)| (name.as_ref(), interface, false)))
+	/// Returns each interface, whether it's a bridge, and whether it's a VLAN that's nested under its parent, in display order.
+	pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&'_ str, &'_ mut Interface, bool, bool)> {
+		self.inner.iter_mut().map(|(name, interface, is_bridge, is_nested)| (&**name, interface, *is_bridge, *is_nested))
 	}
 
-	pub(crate) fn names(&self) -> impl Iterator<Item = &'_ str> {
-		self.gateways.keys().map(AsRef::as_ref)
-		.chain(self.bridges.keys().map(AsRef::as_ref))
-		.chain(self.other.keys().map(AsRef::as_ref))
+	/// Returns each interface's name, and whether it's a VLAN that's nested under its parent.
+	pub(crate) fn names(&self) -> impl Iterator<Item = (&'_ str, bool)> {
+		self.inner.iter().map(|(name, _, _, is_nested)| (name.as_ref(), *is_nested))
 	}
 
 	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
-		for (_, interface, _) in self.iter_mut() {
+		for (_, interface, _, _) in self.iter_mut() {
 			interface.addresses.clear();
 
-			interface.received_bytes_previous = interface.received_bytes;
-			interface.received_bytes = 0;
+			interface.counters_previous = interface.counters;
+			interface.counters = Default::default();
 
-			interface.sent_bytes_previous = interface.sent_bytes;
-			interface.sent_bytes = 0;
+			let (error, link) = interface.ifconfig_exec.run(session)?;
+			interface.error =
+				if error.is_none() && !link.flags.iter().any(|flag| flag == "UP") {
+					Some("administratively down".to_owned())
+				}
+				else {
+					error
+				};
+			interface.link = link;
 
-			interface.error = interface.ifconfig_exec.run(session)?;
+			if let Some((port, port_ifconfig_exec)) = &interface.port_ifconfig_exec {
+				// A PPP interface like `pppoe0` doesn't have a carrier or media of its own, so get those from its physical port.
+				let (port_error, port_link) = port_ifconfig_exec.run(session)?;
+				if interface.error.is_none() {
+					interface.error =
+						if let Some(port_error) = port_error {
+							Some(format!("{}: {}", port, port_error))
+						}
+						else if !interface.link.flags.iter().any(|flag| flag == "RUNNING") {
+							Some("not connected".to_owned())
+						}
+						else {
+							None
+						};
+				}
+				interface.link.media = port_link.media;
+			}
 		}
 
-		let interface_statistics = crate::ssh_exec::netstat_bin::get_interfaces(session)?;
+		let interface_statistics = crate::ssh_exec::netstat_bdin::get_interfaces(session)?;
 
 		for interface_statistics in interface_statistics {
 			let interface_name = interface_statistics.name;
-			#[allow(clippy::option_if_let_else)]
 			let interface =
-				if let Some(interface) = self.gateways.get_mut(&interface_name) {
-					Some(interface)
-				}
-				else if let Some(interface) = self.bridges.get_mut(&interface_name) {
-					Some(interface)
-				}
-				else if let Some(interface) = self.other.get_mut(&interface_name) {
-					Some(interface)
-				}
-				else {
-					None
-				};
+				self.inner.iter_mut()
+				.find(|(name, ..)| *name == interface_name)
+				.map(|(_, interface, _, _)| interface);
 
 			if let Some(interface) = interface {
 				if interface_statistics.network.starts_with("<Link#") {
-					interface.received_bytes += interface_statistics.received_bytes;
-					interface.sent_bytes += interface_statistics.sent_bytes;
+					let counters = &mut interface.counters;
+					counters.received_bytes += interface_statistics.received_bytes;
+					counters.sent_bytes += interface_statistics.sent_bytes;
+					counters.received_packets += interface_statistics.received_packets;
+					counters.sent_packets += interface_statistics.sent_packets;
+					counters.errors += interface_statistics.received_errors + interface_statistics.send_errors + interface_statistics.collisions;
+					counters.dropped_packets += interface_statistics.dropped_packets;
 				}
 				else if !interface_statistics.address.starts_with("fe80:") {
 					let address = interface_statistics.address.parse()?;
@@ -75,37 +132,87 @@ impl Interfaces {
 	}
 }
 
+/// Display labels of interfaces, like `LAN (igb1)`
+#[derive(Debug)]
+pub(crate) struct Labels(std::collections::BTreeMap<String, String>);
+
+impl Labels {
+	pub(crate) fn new(mut descriptions: std::collections::BTreeMap<String, String>, config: Option<&crate::config::Interfaces>) -> Self {
+		if let Some(config) = config {
+			descriptions.extend(config.labels.iter().map(|(name, label)| (name.clone(), label.clone())));
+		}
+
+		Labels(descriptions.into_iter().map(|(name, description)| {
+			let label = format!("{} ({})", description, name);
+			(name, label)
+		}).collect())
+	}
+
+	/// Returns the label of the given interface, or just its name if it doesn't have one.
+	pub(crate) fn get<'a>(&'a self, name: &'a str) -> &'a str {
+		self.0.get(name).map_or(name, AsRef::as_ref)
+	}
+}
+
 #[derive(Debug)]
 pub(crate) struct Interface {
 	ifconfig_exec: crate::ssh_exec::ifconfig::Exec,
 
+	/// The physical port of a PPP interface, and the exec to get its carrier and media
+	port_ifconfig_exec: Option<(String, crate::ssh_exec::ifconfig::Exec)>,
+
 	pub(crate) error: Option<String>,
 
+	pub(crate) link: crate::ssh_exec::ifconfig::Link,
+	minimum_speed_mbps: u64,
+
+	pub(crate) topology: Option<crate::pfconfig::Topology>,
+
 	addresses: std::collections::BTreeSet<InterfaceAddressOrdered>,
 
-	received_bytes_previous: u64,
-	received_bytes: u64,
+	counters_previous: Counters,
+	counters: Counters,
+}
 
-	sent_bytes_previous: u64,
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+	received_bytes: u64,
 	sent_bytes: u64,
+	received_packets: u64,
+	sent_packets: u64,
+
+	// Input and output errors, and collisions
+	errors: u64,
+
+	// Input queue drops
+	dropped_packets: u64,
 }
 
 impl Interface {
-	fn new(name: &str) -> Self {
+	fn new(name: &str, topology: Option<crate::pfconfig::Topology>, minimum_speed_mbps: u64) -> Self {
 		let ifconfig_exec = crate::ssh_exec::ifconfig::Exec::new(name);
 
+		let port_ifconfig_exec = match &topology {
+			Some(crate::pfconfig::Topology::Ppp { ports, .. }) =>
+				ports.first().map(|port| (port.clone(), crate::ssh_exec::ifconfig::Exec::new(port))),
+			_ => None,
+		};
+
 		Interface {
 			ifconfig_exec,
+			port_ifconfig_exec,
 
 			error: None,
 
-			addresses: Default::default(),
+			link: Default::default(),
+			minimum_speed_mbps,
 
-			received_bytes_previous: 0,
-			received_bytes: 0,
+			topology,
 
-			sent_bytes_previous: 0,
-			sent_bytes: 0,
+			addresses: Default::default(),
+
+			counters_previous: Default::default(),
+			counters: Default::default(),
 		}
 	}
 
@@ -114,15 +221,64 @@ impl Interface {
 	}
 
 	pub(crate) fn speed(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32)> {
-		if self.received_bytes_previous > 0 && self.sent_bytes_previous > 0 {
-			let interface_received_speed = (self.received_bytes.saturating_sub(self.received_bytes_previous)) as f32 / time_since_previous.as_secs() as f32 * 8.;
-			let interface_sent_speed = (self.sent_bytes.saturating_sub(self.sent_bytes_previous)) as f32 / time_since_previous.as_secs() as f32 * 8.;
+		if self.counters_previous.received_bytes > 0 && self.counters_previous.sent_bytes > 0 {
+			let interface_received_speed = crate::per_second(self.counters.received_bytes, self.counters_previous.received_bytes, time_since_previous) * 8.;
+			let interface_sent_speed = crate::per_second(self.counters.sent_bytes, self.counters_previous.sent_bytes, time_since_previous) * 8.;
 			Some((interface_received_speed, interface_sent_speed))
 		}
 		else {
 			None
 		}
 	}
+
+	/// The higher of the received and sent bitrates, as a percentage of the negotiated link speed.
+	pub(crate) fn utilization_percent(&self, time_since_previous: std::time::Duration) -> Option<f32> {
+		let (interface_received_speed, interface_sent_speed) = self.speed(time_since_previous)?;
+		let link_speed = self.link.media.as_ref()?.speed_mbps? as f32 * 1_000_000.;
+		Some(interface_received_speed.max(interface_sent_speed) * 100. / link_speed)
+	}
+
+	/// Whether the link negotiated half-duplex, or a lower speed than configured.
+	pub(crate) fn is_link_degraded(&self) -> bool {
+		self.link.media.as_ref().is_some_and(|media|
+			media.duplex == Some(crate::ssh_exec::ifconfig::Duplex::Half) ||
+			media.speed_mbps.is_some_and(|speed_mbps| speed_mbps < self.minimum_speed_mbps))
+	}
+
+	/// Members of a LAGG interface that are configured but not active.
+	pub(crate) fn inactive_lagg_members(&self) -> impl Iterator<Item = &'_ str> {
+		let members = match &self.topology {
+			Some(crate::pfconfig::Topology::Lagg { members, .. }) => &members[..],
+			_ => &[],
+		};
+		members.iter()
+			.filter(move |member| !self.link.lagg_ports.iter().any(|(port, is_active)| port == *member && *is_active))
+			.map(AsRef::as_ref)
+	}
+
+	/// Received and sent packets per second.
+	pub(crate) fn packet_rate(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32)> {
+		if self.counters_previous.received_packets > 0 && self.counters_previous.sent_packets > 0 {
+			let interface_received_packet_rate = crate::per_second(self.counters.received_packets, self.counters_previous.received_packets, time_since_previous);
+			let interface_sent_packet_rate = crate::per_second(self.counters.sent_packets, self.counters_previous.sent_packets, time_since_previous);
+			Some((interface_received_packet_rate, interface_sent_packet_rate))
+		}
+		else {
+			None
+		}
+	}
+
+	/// Errors (including collisions) and dropped packets per second.
+	pub(crate) fn error_rate(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32)> {
+		if self.counters_previous.received_packets > 0 && self.counters_previous.sent_packets > 0 {
+			let interface_error_rate = crate::per_second(self.counters.errors, self.counters_previous.errors, time_since_previous);
+			let interface_drop_rate = crate::per_second(self.counters.dropped_packets, self.counters_previous.dropped_packets, time_since_previous);
+			Some((interface_error_rate, interface_drop_rate))
+		}
+		else {
+			None
+		}
+	}
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
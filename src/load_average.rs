@@ -0,0 +1,37 @@
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct LoadAverage {
+	pub(crate) one: f32,
+	pub(crate) five: f32,
+	pub(crate) fifteen: f32,
+	pub(crate) processes_total: usize,
+	pub(crate) processes_running: usize,
+}
+
+impl LoadAverage {
+	pub(crate) fn update(&mut self, reader: &mut impl std::io::Read) -> Result<(), crate::Error> {
+		// struct loadavg { fixpt_t ldavg[3]; long fscale; }
+		let one: crate::c_uint = crate::Parse::parse(reader)?;
+		let five: crate::c_uint = crate::Parse::parse(reader)?;
+		let fifteen: crate::c_uint = crate::Parse::parse(reader)?;
+
+		// fscale is aligned to the size of a long
+		let padding = (std::mem::size_of::<crate::c_ulong>() - (3 * std::mem::size_of::<crate::c_uint>()) % std::mem::size_of::<crate::c_ulong>()) % std::mem::size_of::<crate::c_ulong>();
+		let () = std::io::Read::read_exact(reader, &mut vec![0_u8; padding])?;
+
+		let fscale: crate::c_ulong = crate::Parse::parse(reader)?;
+		let fscale = fscale as f32;
+
+		self.one = one as f32 / fscale;
+		self.five = five as f32 / fscale;
+		self.fifteen = fifteen as f32 / fscale;
+
+		Ok(())
+	}
+
+	pub(crate) fn update_processes(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		let crate::ssh_exec::ps::ProcessCounts { total, running } = crate::ssh_exec::ps::get_process_counts(session)?;
+		self.processes_total = total;
+		self.processes_running = running;
+		Ok(())
+	}
+}
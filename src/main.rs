@@ -4,6 +4,7 @@
 	clippy::cast_precision_loss,
 	clippy::cognitive_complexity,
 	clippy::default_trait_access,
+	clippy::duration_suboptimal_units, // Suggests `Duration::from_mins` etc, which need Rust 1.91
 	clippy::items_after_statements,
 	clippy::let_and_return,
 	clippy::let_unit_value,
@@ -13,18 +14,34 @@
 
 mod config;
 mod pfconfig;
+mod reverse_dns;
 mod ssh_exec;
 
 mod boot_time;
+mod carp;
 mod cpu;
+mod cpu_frequency;
+mod dhcp;
 mod disk;
+mod filesystem;
+mod firewall_log_export;
 mod firewall_logs;
+mod firewall_rules;
 mod gateway;
 mod interface;
+mod load_average;
 mod memory;
+mod scan_detection;
 mod service;
+mod shaper;
+mod state_table;
+mod syslog_receiver;
 mod temperature_sysctl;
+mod tty;
+mod unbound;
 mod version_info;
+mod vpn;
+mod zfs;
 
 use std::io::Write;
 
@@ -90,34 +107,90 @@ fn main() -> Result<(), Error> {
 
 	let mut cpu = cpu::Cpu::new();
 
-	let (boot_time, mut memory) = ssh_exec::batched_sysctls_1::run(&session)?;
+	let (boot_time, mut memory, num_cpus, cpu_model) = ssh_exec::batched_sysctls_1::run(&session)?;
+	memory.probe(&session)?;
 
-	let mut disks = disk::Disk::get_all(&session)?;
+	let mut cpu_frequency = cpu_frequency::CpuFrequency::get(&session)?;
+
+	let mut load_average = Default::default();
+
+	let mut disks = disk::Disk::get_all(config.disks, &session)?;
 	let max_disk_name_len = disks.iter().map(|disk::Disk { name, .. }| name.len()).max().unwrap_or_default();
 	let max_disk_serial_number_len = disks.iter().map(|disk::Disk { serial_number, .. }| serial_number.len()).max().unwrap_or_default();
 
+	let mut zfs_pools = zfs::Pool::get_all(&session)?;
+	let max_zfs_pool_name_len = zfs_pools.iter().map(|zfs::Pool { name, .. }| name.len()).max().unwrap_or_default();
+
+	let mut arc_stats = Default::default();
+
 	let mut temperature_sysctls = temperature_sysctl::TemperatureSysctl::get_all(&session)?;
 
-	let batched_sysctls_exec = ssh_exec::batched_sysctls_2::Exec::new(&temperature_sysctls[..]);
+	let batched_sysctls_exec = ssh_exec::batched_sysctls_2::Exec::new(&memory, &cpu_frequency, &temperature_sysctls[..]);
 
 	let max_thermal_sensor_name_len =
 		temperature_sysctls.iter().map(|temperature_sysctl::TemperatureSysctl { name, .. }| name)
-		.chain(disks.iter().map(|disk::Disk { name, .. }| name))
+		.chain(disks.iter().filter(|disk| disk.smart_available()).map(|disk::Disk { name, .. }| name))
 		.map(String::len).max().unwrap_or_default();
 
-	let mut interfaces = interface::Interfaces::new(pfconfig.gateway_interfaces.iter().cloned(), pfconfig.bridge_interfaces, pfconfig.other_interfaces);
-	let max_interface_name_len = interfaces.names().map(str::len).max().unwrap_or_default();
+	let interface_labels = interface::Labels::new(pfconfig.interface_descriptions, config.interfaces.as_ref());
 
-	let mut gateways = gateway::Gateways::new(pfconfig.gateways);
+	let mut interfaces = interface::Interfaces::new(config.interfaces, &pfconfig.interface_topologies, pfconfig.gateway_interfaces.iter().cloned(), pfconfig.bridge_interfaces, pfconfig.other_interfaces);
+	let max_interface_name_len =
+		interfaces.names()
+		.map(|(name, is_nested)| interface_label(&interface_labels, name, is_nested).chars().count())
+		.max().unwrap_or_default();
+
+	let mut gateways = gateway::Gateways::new(pfconfig.gateways, pfconfig.gateway_groups, pfconfig.default_gateway_v4, pfconfig.default_gateway_v6);
+
+	let routes_config = config.routes.unwrap_or_default();
 	let max_gateway_name_len = gateways.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+	let num_gateway_groups = gateways.groups().count();
+	let max_gateway_group_name_len = gateways.groups().map(|(name, _)| name.len()).max().unwrap_or_default();
+
+	let mut vpn_tunnels = vpn::Tunnels::new(pfconfig.vpn_tunnels);
+	let max_vpn_tunnel_description_len = vpn_tunnels.iter().map(|tunnel| tunnel.description().len()).max().unwrap_or_default();
+
+	let mut shaper = shaper::Shaper::new(pfconfig.shaper_queues, pfconfig.limiters, &interface_labels);
+	let max_shaper_queue_label_len = shaper.altq_queues().iter().chain(shaper.limiters()).map(|queue| queue.label.len()).max().unwrap_or_default();
+
+	let mut unbound = unbound::Unbound::new(pfconfig.dns_resolver_enabled);
+
+	let mut state_table = state_table::StateTable::new(&config.states.unwrap_or_default());
+
+	let mut high_availability =
+		carp::HighAvailability::new(pfconfig.carp_vips, pfconfig.pfsync_enabled, config.high_availability.unwrap_or_default(), &config.ssh.username);
+
+	let dhcp_static_mappings = pfconfig.dhcp_static_mappings;
+	let mut dhcp_leases = config.dhcp_leases.as_ref().map(|dhcp_leases_config| dhcp::Leases::new(dhcp_static_mappings, dhcp_leases_config));
+
+	let rule_descriptions = pfconfig.rule_descriptions;
+	let table_aliases = pfconfig.table_aliases;
+	let mut firewall_rules =
+		config.firewall_rules.as_ref()
+		.map(|firewall_rules_config| firewall_rules::FirewallRules::new(rule_descriptions, table_aliases, firewall_rules_config));
+
+	let filesystems = filesystem::Filesystems::new(config.filesystems.unwrap_or_default());
 
 	let mut services = service::Service::get_all(config.services, pfconfig.services)?;
 	let max_service_name_len = services.iter().map(|service::Service { name, .. }| name.len()).max().unwrap_or_default();
 	let num_services_per_row = 70 / (max_service_name_len + 2);
 	let num_services_rows = (services.len() + num_services_per_row - 1) / num_services_per_row;
 
-	let max_firewall_log_interface_name_len = pfconfig.gateway_interfaces.iter().map(String::len).max().unwrap_or_default();
-	let firewall_logs = firewall_logs::Logs::new(pfconfig.gateway_interfaces, &config.ssh)?;
+	let firewall_logs_config = config.firewall_logs.unwrap_or_default();
+	let max_firewall_log_interface_name_len = pfconfig.gateway_interfaces.iter().map(|name| interface_labels.get(name).len()).max().unwrap_or_default();
+
+	// Held until `main` returns, so that the terminal is restored even if it returns an error.
+	// Enabled before the firewall log threads are started so that they can also restore it if they exit the process.
+	let raw_mode = tty::RawMode::enable()?;
+
+	let firewall_logs =
+		firewall_logs::Logs::new(pfconfig.gateway_interfaces, &config.ssh, &firewall_logs_config, raw_mode.as_ref().map(tty::RawMode::original))?;
+
+	let reverse_dns = firewall_logs_config.reverse_dns.as_ref().map(reverse_dns::Resolver::new);
+
+	if let Some(raw_mode) = &raw_mode {
+		firewall_logs::Logs::spawn_key_reader_thread(firewall_logs.clone(), raw_mode.original());
+	}
 
 
 	let mut previous = std::time::SystemTime::now();
@@ -130,20 +203,48 @@ fn main() -> Result<(), Error> {
 			.map_err(|err| format!("could not calculate time since previous iteration: {}", err))?;
 
 
-		batched_sysctls_exec.run(&mut cpu, &mut memory, &mut temperature_sysctls[..], &session)?;
+		batched_sysctls_exec.run(&mut cpu, &mut memory, &mut arc_stats, &mut load_average, &mut cpu_frequency, &mut temperature_sysctls[..], &session)?;
+
+		load_average.update_processes(&session)?;
+
+		let swap = ssh_exec::swapinfo::get_swap(&session)?;
 
 		for disk in &mut disks[..] {
 			disk.update(&session)?;
 		}
 
+		zfs::Pool::update_all(&mut zfs_pools, &session)?;
+
 		interfaces.update(&session)?;
 
+		state_table.update(&session)?;
+
 		gateways.update(&session)?;
 
+		let routes = ssh_exec::netstat_rn::get_routes(&session)?;
+
+		unbound.update(&session)?;
+
+		vpn_tunnels.update(&session)?;
+
+		shaper.update(&session)?;
+
+		if !high_availability.is_empty() {
+			high_availability.update(&session)?;
+		}
+
+		if let Some(firewall_rules) = &mut firewall_rules {
+			firewall_rules.update(&session)?;
+		}
+
 		for service in &mut services[..] {
 			service.update(&session)?;
 		}
 
+		if let Some(dhcp_leases) = &mut dhcp_leases {
+			dhcp_leases.update(&session)?;
+		}
+
 
 		// Note:
 		//
@@ -179,6 +280,33 @@ fn main() -> Result<(), Error> {
 		}
 
 
+		{
+			let load_average::LoadAverage { one, five, fifteen, processes_total, processes_running } = load_average;
+			let load_percent = one * 100. / num_cpus as f32;
+			let load_color = get_color_for_usage(load_percent);
+			write!(
+				output,
+				"\n\x1B[KLoad average  : \x1B[{}m{:5.2} {:5.2} {:5.2} ({:5.1} % of {} cores)\x1B[0m, {} processes ({} running)",
+				load_color,
+				one,
+				five,
+				fifteen,
+				load_percent,
+				num_cpus,
+				processes_total,
+				processes_running,
+			)?;
+		}
+
+
+		{
+			write!(output, "\n\x1B[KCPU           : {}", cpu_model)?;
+			if cpu_frequency.exists {
+				write!(output, " at {} MHz", cpu_frequency.mhz)?;
+			}
+		}
+
+
 		output.extend_from_slice(b"\n\x1B[K");
 
 
@@ -191,20 +319,184 @@ fn main() -> Result<(), Error> {
 			else {
 				output.extend_from_slice(b"    ? %");
 			}
+
+			if let Some(cpu::CpuStates { user, nice, system, interrupt, idle }) = cpu.states() {
+				// A NIC's interrupts are usually handled by one core, so a single core that's busy with them
+				// is diluted in the aggregate figure. Highlight it if any core is.
+				let busiest_interrupt_core =
+					cpu.cores_states().enumerate()
+					.filter_map(|(core_num, core_states)| Some((core_num, core_states?.interrupt)))
+					.filter(|&(_, core_interrupt)| is_interrupt_high(core_interrupt))
+					.max_by(|(_, a), (_, b)| a.total_cmp(b));
+				let interrupt_color = if is_interrupt_high(interrupt) || busiest_interrupt_core.is_some() { "1;31" } else { "0" };
+				write!(
+					output,
+					"   user {:5.1} %   nice {:5.1} %   system {:5.1} %   \x1B[{}minterrupt {:5.1} %\x1B[0m   idle {:5.1} %",
+					user,
+					nice,
+					system,
+					interrupt_color,
+					interrupt,
+					idle,
+				)?;
+
+				if let Some((core_num, core_interrupt)) = busiest_interrupt_core {
+					write!(output, " \x1B[1;31m(core {} interrupt {:5.1} %)\x1B[0m", core_num, core_interrupt)?;
+				}
+			}
+		}
+
+
+		{
+			output.extend_from_slice(b"\n\x1B[KCPU cores     : ");
+			for (i, (core_usage_percent, core_states)) in cpu.cores_usage_percent().zip(cpu.cores_states()).enumerate() {
+				if i > 0 {
+					if i % 8 == 0 {
+						output.extend_from_slice(b"\n\x1B[K                ");
+					}
+					else {
+						output.extend_from_slice(b" ");
+					}
+				}
+
+				if let Some(core_usage_percent) = core_usage_percent {
+					let core_usage_color =
+						if core_states.is_some_and(|core_states| is_interrupt_high(core_states.interrupt)) {
+							"1;31"
+						}
+						else {
+							get_color_for_usage(core_usage_percent)
+						};
+					write!(output, "\x1B[{}m{:5.1} %\x1B[0m", core_usage_color, core_usage_percent)?;
+				}
+				else {
+					output.extend_from_slice(b"    ? %");
+				}
+			}
 		}
 
 
 		{
 			let (memory_usage_percent, memory_usage_color) = usage(memory.used_pages as f32, memory.num_pages as f32);
 			write!(output, "\n\x1B[KMemory usage  : \x1B[{}m{:5.1} % of {} MiB\x1B[0m", memory_usage_color, memory_usage_percent, memory.physical / 1_048_576)?;
+
+			#[allow(clippy::cast_possible_truncation)] // ARC size is bounded by the number of wired pages
+			let arc_pages = (memory.arc_bytes / u64::from(memory.page_size)).min(memory.wired_pages.into()) as c_uint;
+
+			let mut segments = vec![
+				("wired", memory.wired_pages - arc_pages, "0;31"),
+			];
+			if memory.has_arc {
+				segments.push(("ARC", arc_pages, "0;36"));
+			}
+			segments.push(("active", memory.active_pages, "1;33"));
+			segments.push(("inactive", memory.inactive_pages, "1;34"));
+			if memory.has_laundry {
+				segments.push(("laundry", memory.laundry_pages, "0;35"));
+			}
+			segments.push(("free", memory.free_pages, "1;32"));
+
+			output.extend_from_slice(b"\n\x1B[K                ");
+			let total_pages: u64 = segments.iter().map(|&(_, pages, _)| u64::from(pages)).sum();
+			let mut cumulative_pages = 0;
+			let mut bar_len = 0;
+			for &(_, pages, color) in &segments {
+				// Round the cumulative width rather than each segment's width, so that the bar is always the same length.
+				cumulative_pages += u64::from(pages);
+				let new_bar_len = (cumulative_pages * MEMORY_BAR_LEN + total_pages / 2) / total_pages.max(1);
+				write!(output, "\x1B[{}m", color)?;
+				for _ in bar_len..new_bar_len {
+					output.extend_from_slice("\u{2588}".as_bytes());
+				}
+				bar_len = new_bar_len;
+			}
+			output.extend_from_slice(b"\x1B[0m");
+
+			output.extend_from_slice(b"\n\x1B[K                ");
+			for (i, &(name, pages, color)) in segments.iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"  ");
+				}
+				write!(output, "\x1B[{}m{} {} MiB\x1B[0m", color, name, memory.pages_to_mib(pages))?;
+			}
 		}
 
 
 		{
-			let states_used = ssh_exec::pfctl_s_info::get_states_used(&session)?;
-			let states_max = (memory.physical / 10_485_760) * 1000;
+			let ssh_exec::swapinfo::Swap { num_devices, total_kib, used_kib } = swap;
+			if num_devices == 0 {
+				output.extend_from_slice(b"\n\x1B[KSwap usage    : no swap");
+			}
+			else {
+				// Any swap use at all on a router is a sign that it's short of memory.
+				let swap_usage_percent = used_kib as f32 * 100. / total_kib as f32;
+				let swap_usage_color = if used_kib > 0 { "1;33" } else { "1;32" };
+				write!(
+					output,
+					"\n\x1B[KSwap usage    : \x1B[{}m{:5.1} % of {} MiB ({} MiB used)\x1B[0m",
+					swap_usage_color,
+					swap_usage_percent,
+					total_kib / 1024,
+					used_kib / 1024,
+				)?;
+			}
+		}
+
+
+		{
+			let states_used = state_table.used();
+			let states_max = state_table.limit;
 			let (states_usage_percent, states_usage_color) = usage(states_used as f32, states_max as f32);
 			write!(output, "\n\x1B[KStates table  : \x1B[{}m{:5.1} % ({:7} / {:7})\x1B[0m", states_usage_color, states_usage_percent, states_used, states_max)?;
+
+			match state_table.rates(time_since_previous) {
+				Some(state_table::Rates { searches, inserts, removals, matches }) => write!(
+					output,
+					"\n\x1B[K                {} searches/s {} inserts/s {} removals/s {} matches/s",
+					HumanSizeBase10(searches),
+					HumanSizeBase10(inserts),
+					HumanSizeBase10(removals),
+					HumanSizeBase10(matches),
+				)?,
+
+				None => output.extend_from_slice(b"\n\x1B[K                    ?   searches/s     ?   inserts/s     ?   removals/s     ?   matches/s"),
+			}
+
+			output.extend_from_slice(b"\n\x1B[K               ");
+
+			write_counters(&mut output, state_table.counters(), "no drops")?;
+
+			if let Some(state_table::TopTalkers { sources: top_sources, destinations: top_destinations }) = state_table.top_talkers() {
+				let max_talker_address_len =
+					top_sources.iter().chain(top_destinations)
+					.map(|talker| talker.address.to_string().len())
+					.max().unwrap_or_default();
+				let max_talker_interface_len =
+					top_sources.iter().chain(top_destinations)
+					.map(|talker| interface_labels.get(&talker.interface).len())
+					.max().unwrap_or_default();
+
+				for &(title, talkers) in &[("Top sources   : ", top_sources), ("Top dests     : ", top_destinations)] {
+					write!(output, "\n\x1B[K{}", title)?;
+
+					for (i, talker) in talkers.iter().enumerate() {
+						if i > 0 {
+							output.extend_from_slice(b"\n\x1B[K                ");
+						}
+
+						write!(
+							output,
+							"{:>max_talker_address_len$} on {:max_talker_interface_len$} : {:6} states {}B",
+							talker.address.to_string(),
+							interface_labels.get(&talker.interface),
+							talker.num_states,
+							HumanSizeBase10(talker.bytes as f32),
+							max_talker_address_len = max_talker_address_len,
+							max_talker_interface_len = max_talker_interface_len,
+						)?;
+					}
+				}
+			}
 		}
 
 
@@ -217,33 +509,48 @@ fn main() -> Result<(), Error> {
 
 		{
 			output.extend_from_slice(b"\n\x1B[KDisk usage    : ");
-			let filesystems = ssh_exec::df::get_filesystems(&session)?;
-			let max_mount_point_len = filesystems.iter().map(|filesystem| filesystem.mounted_on.len()).max().unwrap_or_default();
-			for (i, filesystem) in filesystems.into_iter().enumerate() {
-				let filesystem_space_used = filesystem.used_blocks;
-				let filesystem_space_max = filesystem.total_blocks;
-				let (filesystem_space_usage_percent, filesystem_space_usage_color) = usage(filesystem_space_used as f32, filesystem_space_max as f32);
+			let mounted_filesystems = filesystems.get(&session)?;
+			let max_mount_point_len = mounted_filesystems.iter().map(|filesystem| filesystem.mounted_on.len()).max().unwrap_or_default();
+			for (i, filesystem) in mounted_filesystems.into_iter().enumerate() {
+				let thresholds = filesystems.thresholds(&filesystem.mounted_on);
+				let filesystem_usage = |used: u64, max: u64| {
+					let percent = if max == 0 { 0. } else { used as f32 * 100. / max as f32 };
+					let color = thresholds.map_or_else(|| get_color_for_usage(percent), |thresholds| get_color_for_thresholds(percent, thresholds));
+					(percent, color)
+				};
+				let (filesystem_space_usage_percent, filesystem_space_usage_color) = filesystem_usage(filesystem.used_blocks, filesystem.total_blocks);
+				let (filesystem_inode_usage_percent, filesystem_inode_usage_color) =
+					filesystem_usage(filesystem.used_inodes, filesystem.used_inodes + filesystem.free_inodes);
 				if i > 0 {
 					output.extend_from_slice(b"\n\x1B[K                ");
 				}
 
 				write!(output,
-					"\x1B[{}m{:>max_mount_point_len$} : {:5.1} % of {}B\x1B[0m",
-					filesystem_space_usage_color,
+					"{:>max_mount_point_len$} : \x1B[{}m{:5.1} % of {}B\x1B[0m, inodes \x1B[{}m{:5.1} %\x1B[0m",
 					filesystem.mounted_on,
+					filesystem_space_usage_color,
 					filesystem_space_usage_percent,
 					HumanSizeBase10(filesystem.total_blocks as f32 * 1024.),
+					filesystem_inode_usage_color,
+					filesystem_inode_usage_percent,
 					max_mount_point_len = max_mount_point_len,
 				)?;
+
+				if let Some(config::UsageThresholds { warning, critical }) = thresholds {
+					write!(output, " (warning at {} %, critical at {} %)", warning, critical)?;
+				}
 			}
 		}
 
 
 		{
 			output.extend_from_slice(b"\n\x1B[KSMART status  : ");
-			for (i, disk::Disk { name, serial_number, smart_passed, .. }) in disks.iter().enumerate() {
-				let disk_status_color = get_color_for_up_down(*smart_passed);
-				let disk_smart_status = if *smart_passed { "PASSED" } else { "FAILED" };
+			for (i, disk) in disks.iter().enumerate() {
+				let (disk_status_color, disk_smart_status) = match disk.smart_passed {
+					Some(true) => (get_color_for_up_down(true), "PASSED"),
+					Some(false) => (get_color_for_up_down(false), "FAILED"),
+					None => ("1;33", "SMART unavailable"),
+				};
 
 				if i > 0 {
 					output.extend_from_slice(b"\n\x1B[K                ");
@@ -253,12 +560,102 @@ fn main() -> Result<(), Error> {
 					output,
 					"\x1B[{}m{:>max_disk_name_len$} {:max_disk_serial_number_len$} {}\x1B[0m",
 					disk_status_color,
-					name,
-					serial_number,
+					disk.name,
+					disk.serial_number,
 					disk_smart_status,
 					max_disk_name_len = max_disk_name_len,
 					max_disk_serial_number_len = max_disk_serial_number_len,
 				)?;
+
+				if let Some(power_on_hours) = disk.power_on_hours {
+					write!(output, " {:6} h", power_on_hours)?;
+				}
+
+				if let Some(percentage_used) = disk.percentage_used {
+					write!(output, " \x1B[{}m{:3} % worn\x1B[0m", get_color_for_usage(percentage_used.into()), percentage_used)?;
+				}
+
+				if let Some((available_spare, available_spare_threshold)) = disk.available_spare {
+					let available_spare_color = get_color_for_up_down(available_spare > available_spare_threshold);
+					write!(output, " \x1B[{}m{:3} % spare\x1B[0m", available_spare_color, available_spare)?;
+				}
+
+				// Non-zero error counters are a warning, and ones that are still increasing are a sign of imminent failure.
+				for counter in &disk.error_counters {
+					if counter.value == 0 {
+						continue;
+					}
+
+					let increase = counter.increase();
+					if increase > 0 {
+						write!(output, " \x1B[0;31m{} {} (+{})\x1B[0m", counter.name, counter.value, increase)?;
+					}
+					else {
+						write!(output, " \x1B[1;33m{} {}\x1B[0m", counter.name, counter.value)?;
+					}
+				}
+			}
+		}
+
+
+		if !zfs_pools.is_empty() {
+			output.extend_from_slice(b"\n\x1B[KZFS pools     : ");
+			for (i, pool) in zfs_pools.iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				let pool_health_color = match &*pool.health {
+					"ONLINE" => "1;32",
+					"DEGRADED" => "1;33",
+					_ => "0;31",
+				};
+
+				let (pool_usage_percent, pool_usage_color) = usage(pool.used as f32, (pool.used + pool.available) as f32);
+
+				write!(
+					output,
+					"{:>max_zfs_pool_name_len$} : \x1B[{}m{:8}\x1B[0m \x1B[{}m{:5.1} % of {}B\x1B[0m",
+					pool.name,
+					pool_health_color,
+					pool.health,
+					pool_usage_color,
+					pool_usage_percent,
+					HumanSizeBase10((pool.used + pool.available) as f32),
+					max_zfs_pool_name_len = max_zfs_pool_name_len,
+				)?;
+
+				match pool.fragmentation_percent {
+					Some(fragmentation_percent) => write!(output, ", frag {:3} %", fragmentation_percent)?,
+					None => output.extend_from_slice(b", frag   - %"),
+				}
+
+				match &pool.scan {
+					zfs::Scan::None => output.extend_from_slice(b", never scrubbed"),
+					zfs::Scan::InProgress => output.extend_from_slice(b", scrub in progress"),
+					zfs::Scan::Finished { errors, days_ago } => {
+						let scrub_color = get_color_for_up_down(*errors == 0);
+						write!(output, ", \x1B[{}mlast scrub {} errors\x1B[0m", scrub_color, errors)?;
+						if let Some(days_ago) = days_ago {
+							write!(output, " {} days ago", days_ago)?;
+						}
+					},
+					zfs::Scan::Other(scan) => write!(output, ", {}", scan)?,
+				}
+			}
+		}
+
+
+		if memory.has_arc {
+			write!(
+				output,
+				"\n\x1B[KZFS ARC       : {} MiB of {} MiB max",
+				memory.arc_bytes / 1_048_576,
+				arc_stats.max_bytes / 1_048_576,
+			)?;
+			match arc_stats.hit_ratio_percent() {
+				Some(hit_ratio_percent) => write!(output, ", hit ratio {:5.1} %", hit_ratio_percent)?,
+				None => output.extend_from_slice(b", hit ratio     ? %"),
 			}
 		}
 
@@ -274,7 +671,7 @@ fn main() -> Result<(), Error> {
 					let thermal_sensor_value = *value as f32 / 10. - 273.15;
 					(name, thermal_sensor_value)
 				})
-				.chain(disks.iter().map(|disk::Disk { name, temperature, .. }| {
+				.chain(disks.iter().filter(|disk| disk.smart_available()).map(|disk::Disk { name, temperature, .. }| {
 					let thermal_sensor_value = *temperature as f32;
 					(name, thermal_sensor_value)
 				}));
@@ -304,50 +701,145 @@ fn main() -> Result<(), Error> {
 		{
 			output.extend_from_slice(b"\n\x1B[KInterfaces    : ");
 
-			for (i, (interface_name, interface, is_bridge)) in interfaces.iter_mut().enumerate() {
+			for (i, (interface_name, interface, is_bridge, is_nested)) in interfaces.iter_mut().enumerate() {
 				if i > 0 {
 					output.extend_from_slice(b"\n\x1B[K                ");
 				}
 
-				let interface_status_color = get_color_for_up_down(interface.error.is_none());
+				let interface_error_rate = interface.error_rate(time_since_previous);
+
+				// Errors or drops that are still increasing are as bad as the interface being down.
+				let interface_has_increasing_errors =
+					interface_error_rate.is_some_and(|(interface_error_rate, interface_drop_rate)| interface_error_rate > 0. || interface_drop_rate > 0.);
+
+				let interface_status_color = get_color_for_up_down(interface.error.is_none() && !interface_has_increasing_errors);
 
 				write!(
 					output,
 					"\x1B[{}m{:>max_interface_name_len$} : ",
 					interface_status_color,
-					interface_name,
+					interface_label(&interface_labels, interface_name, is_nested),
 					max_interface_name_len = max_interface_name_len,
 				)?;
 
 				if let Some(interface_error) = &interface.error {
-					write!(output, "{:30}", interface_error)?;
+					write!(output, "{:85}", interface_error)?;
 				}
 				else if is_bridge {
 					// Bridge bandwidth is double-counted, and isn't particularly useful anyway, so don't print it.
-					output.extend_from_slice(b"                              ");
+					write!(output, "{:85}", "")?;
 				}
 				else {
 					match interface.speed(time_since_previous) {
-						Some((interface_received_speed, interface_sent_speed)) =>
-							write!(output, "{}b/s down {}b/s up ", HumanSizeBase10(interface_received_speed), HumanSizeBase10(interface_sent_speed))?,
+						Some((interface_received_speed, interface_sent_speed)) => {
+							let interface_utilization_color =
+								interface.utilization_percent(time_since_previous)
+								.map_or(interface_status_color, get_color_for_usage);
+							write!(
+								output,
+								"\x1B[{}m{}b/s down {}b/s up \x1B[{}m",
+								interface_utilization_color,
+								HumanSizeBase10(interface_received_speed),
+								HumanSizeBase10(interface_sent_speed),
+								interface_status_color,
+							)?;
+						},
 
 						None =>
 							output.extend_from_slice(b"    ?  b/s down     ?  b/s up "),
 					}
+
+					match interface.packet_rate(time_since_previous) {
+						Some((interface_received_packet_rate, interface_sent_packet_rate)) =>
+							write!(output, "{}p/s down {}p/s up ", HumanSizeBase10(interface_received_packet_rate), HumanSizeBase10(interface_sent_packet_rate))?,
+
+						None =>
+							output.extend_from_slice(b"    ?  p/s down     ?  p/s up "),
+					}
+
+					match interface_error_rate {
+						Some((interface_error_rate, interface_drop_rate)) =>
+							write!(output, "{:5.1} err/s {:5.1} drop/s ", interface_error_rate, interface_drop_rate)?,
+
+						None =>
+							output.extend_from_slice(b"    ? err/s     ? drop/s "),
+					}
 				}
 
-				for (i, address) in interface.addresses().enumerate() {
+				// The second row shows the link properties, so every interface has at least two rows even if it has fewer addresses.
+				let num_rows = interface.addresses().count().max(2);
+				let mut addresses = interface.addresses();
+
+				for i in 0..num_rows {
 					if i > 0 {
 						write!(
 							output,
-							"\n\x1B[K                \x1B[{}m{:>max_interface_name_len$}                                 ",
+							"\n\x1B[K                \x1B[{}m{:>max_interface_name_len$}   ",
 							interface_status_color,
 							"",
 							max_interface_name_len = max_interface_name_len,
 						)?;
+
+						if i == 1 {
+							let link = &interface.link;
+
+							let (media_color, media_description) = match &link.media {
+								Some(media) if interface.is_link_degraded() => ("1;33", &*media.description),
+								Some(media) => (interface_status_color, &*media.description),
+								None => (interface_status_color, ""),
+							};
+
+							let mut link_properties = vec![];
+							let mut link_properties_color = interface_status_color;
+							match &interface.topology {
+								Some(pfconfig::Topology::Vlan { parent, tag }) =>
+									link_properties.push(format!("VLAN {} on {}", tag, interface_labels.get(parent))),
+
+								Some(pfconfig::Topology::Lagg { members, protocol }) => {
+									let inactive_members: Vec<_> = interface.inactive_lagg_members().collect();
+									link_properties.push(format!("{} of {}", protocol, members.join(" ")));
+									if !inactive_members.is_empty() {
+										link_properties.push(format!("{} inactive", inactive_members.join(" ")));
+										link_properties_color = "1;33";
+									}
+								},
+
+								Some(pfconfig::Topology::Ppp { r#type, ports }) =>
+									link_properties.push(format!("{} on {}", r#type.to_uppercase(), ports.join(" "))),
+
+								None => (),
+							}
+							if let Some(mtu) = link.mtu {
+								link_properties.push(format!("mtu {}", mtu));
+							}
+							if let Some(mac_address) = &link.mac_address {
+								link_properties.push(mac_address.clone());
+							}
+							if link.flags.iter().any(|flag| flag == "PROMISC") {
+								link_properties.push("promiscuous".to_owned());
+							}
+							let link_properties = link_properties.join(", ");
+
+							write!(
+								output,
+								"\x1B[{}m{:30}\x1B[{}m{:55}\x1B[{}m",
+								media_color,
+								media_description,
+								link_properties_color,
+								link_properties,
+								interface_status_color,
+							)?;
+						}
+						else {
+							write!(output, "{:85}", "")?;
+						}
+					}
+
+					if let Some(address) = addresses.next() {
+						write!(output, "{}", address)?;
 					}
 
-					write!(output, "{}\x1B[0m", address)?;
+					output.extend_from_slice(b"\x1B[0m");
 				}
 			}
 		}
@@ -361,23 +853,426 @@ fn main() -> Result<(), Error> {
 					output.extend_from_slice(b"\n\x1B[K                ");
 				}
 
-				match gateway {
-					Some(gateway::Gateway { latency_average, latency_stddev, ping_packet_loss }) => write!(
+				let (gateway_status_color, gateway_status) = match gateway.status() {
+					gateway::Status::Online => ("1;32", "online"),
+					gateway::Status::Delay | gateway::Status::Loss => ("1;33", "warning"),
+					gateway::Status::Down => ("0;31", "down"),
+				};
+
+				write!(
+					output,
+					"{:>max_gateway_name_len$} : \x1B[{}m{:7}\x1B[0m ",
+					name,
+					gateway_status_color,
+					gateway_status,
+					max_gateway_name_len = max_gateway_name_len,
+				)?;
+
+				match gateway.statistics {
+					Some(gateway::Statistics { latency_average, latency_stddev, ping_packet_loss }) => write!(
 						output,
-						"{:>max_gateway_name_len$} : {:6.1} ms ({:6.1} ms) {:3} %",
-						name,
+						"{:6.1} ms ({:6.1} ms) {:3} %",
 						latency_average.as_secs_f32() * 1000.,
 						latency_stddev.as_secs_f32() * 1000.,
 						ping_packet_loss,
-						max_gateway_name_len = max_gateway_name_len,
 					)?,
 
-					None => write!(
+					None if gateway.is_monitored() => output.extend_from_slice(b"dpinger is not running   "),
+
+					None => output.extend_from_slice(b"monitoring disabled      "),
+				}
+
+				if let Some(monitor) = gateway.monitor() {
+					write!(output, " monitor {}", monitor)?;
+				}
+			}
+		}
+
+
+		if num_gateway_groups > 0 {
+			output.extend_from_slice(b"\n\x1B[KGateway groups: ");
+
+			for (i, (name, members)) in gateways.groups().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				write!(output, "{:>max_gateway_group_name_len$} :", name, max_gateway_group_name_len = max_gateway_group_name_len)?;
+
+				for (gateway_name, tier, is_active) in members {
+					if is_active {
+						write!(output, " \x1B[1;32m{} (tier {}, active)\x1B[0m", gateway_name, tier)?;
+					}
+					else {
+						write!(output, " {} (tier {})", gateway_name, tier)?;
+					}
+				}
+			}
+		}
+
+
+		{
+			output.extend_from_slice(b"\n\x1B[KDefault routes: ");
+
+			for (i, &(is_ipv6, address_family)) in [(false, "IPv4"), (true, "IPv6")].iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				let default_route = routes.iter().find(|route| route.is_ipv6 == is_ipv6 && route.destination == "default");
+				let preferred_default_gateway = gateways.preferred_default_gateway(is_ipv6);
+
+				// After a failover, the default route is through a lower-tier gateway than the one that's configured.
+				let is_preferred = match (default_route, preferred_default_gateway) {
+					(Some(default_route), Some((_, gateway))) => gateway.is_route_via(default_route),
+					(None, Some(_)) => false,
+					(_, None) => true,
+				};
+
+				write!(output, "\x1B[{}m{} : ", get_color_for_up_down(is_preferred), address_family)?;
+
+				match default_route {
+					Some(default_route) => write!(output, "via {} on {}", default_route.gateway, interface_labels.get(&default_route.interface))?,
+					None => output.extend_from_slice(b"none"),
+				}
+
+				if !is_preferred {
+					if let Some((gateway_name, _)) = preferred_default_gateway {
+						write!(output, " (expected {})", gateway_name)?;
+					}
+				}
+
+				output.extend_from_slice(b"\x1B[0m");
+			}
+
+			if routes_config.full_table {
+				output.extend_from_slice(b"\n\x1B[KRoutes        : ");
+
+				let max_route_destination_len = routes.iter().map(|route| route.destination.len()).max().unwrap_or_default();
+				let max_route_gateway_len = routes.iter().map(|route| route.gateway.len()).max().unwrap_or_default();
+
+				for (i, route) in routes.iter().enumerate() {
+					if i > 0 {
+						output.extend_from_slice(b"\n\x1B[K                ");
+					}
+
+					write!(
 						output,
-						"{:>max_gateway_name_len$} : dpinger is not running",
-						name,
-						max_gateway_name_len = max_gateway_name_len,
+						"{:>max_route_destination_len$} via {:max_route_gateway_len$} on {} {}",
+						route.destination,
+						route.gateway,
+						interface_labels.get(&route.interface),
+						route.flags,
+						max_route_destination_len = max_route_destination_len,
+						max_route_gateway_len = max_route_gateway_len,
+					)?;
+				}
+			}
+		}
+
+
+		if unbound.is_enabled {
+			output.extend_from_slice(b"\n\x1B[K");
+
+			output.extend_from_slice(b"\n\x1B[KDNS resolver  : ");
+
+			if unbound.is_running() {
+				match unbound.query_rate(time_since_previous) {
+					Some(query_rate) => write!(output, "{} queries/s", HumanSizeBase10(query_rate))?,
+					None => output.extend_from_slice(b"    ?   queries/s"),
+				}
+
+				match unbound.cache_hit_percent() {
+					Some(cache_hit_percent) => write!(output, " cache hits {:5.1}%", cache_hit_percent)?,
+					None => output.extend_from_slice(b" cache hits     ?%"),
+				}
+
+				match unbound.recursion_time() {
+					Some(recursion_time) => write!(output, " recursion {:7.1} ms", recursion_time.as_secs_f32() * 1000.)?,
+					None => output.extend_from_slice(b" recursion       ? ms"),
+				}
+
+				write_counters(&mut output, unbound.counters(), "no failures")?;
+			}
+			else {
+				output.extend_from_slice(b"\x1B[0;31mnot running\x1B[0m");
+			}
+		}
+
+
+		if !high_availability.is_empty() {
+			output.extend_from_slice(b"\n\x1B[K");
+
+			output.extend_from_slice(b"\n\x1B[KCARP          : ");
+
+			let peer_error = high_availability.peer_error();
+
+			let max_carp_label_len =
+				high_availability.vips().map(|vip| vip.label().len())
+				.chain(high_availability.pfsync.as_ref().map(|_| "pfsync".len()))
+				.chain(peer_error.map(|(peer_hostname, _)| peer_hostname.len()))
+				.max().unwrap_or_default();
+
+			for (i, vip) in high_availability.vips().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				let is_split_brain = vip.is_split_brain();
+
+				let get_color_for_carp_state = |state: Option<&carp::State>| match state {
+					_ if is_split_brain => "0;31",
+					Some(carp::State { status, .. }) => get_color_for_up_down(status == "MASTER" || status == "BACKUP"),
+					None => "0;31",
+				};
+
+				write!(output, "{:>max_carp_label_len$} : ", vip.label(), max_carp_label_len = max_carp_label_len)?;
+
+				match &vip.state {
+					Some(state) => write!(
+						output,
+						"\x1B[{}m{:6}\x1B[0m vhid {:3} advbase {:2} advskew {:3}",
+						get_color_for_carp_state(Some(state)),
+						state.status,
+						vip.vhid(),
+						state.advbase,
+						state.advskew,
+					)?,
+
+					None => write!(output, "\x1B[0;31m{:6}\x1B[0m vhid {:3} {:22}", "absent", vip.vhid(), "")?,
+				}
+
+				if high_availability.has_peer() {
+					match &vip.peer_state {
+						Some(peer_state) => write!(
+							output,
+							" peer \x1B[{}m{:6}\x1B[0m advskew {:3}",
+							get_color_for_carp_state(Some(peer_state)),
+							peer_state.status,
+							peer_state.advskew,
+						)?,
+
+						None => output.extend_from_slice(b" peer ?     "),
+					}
+				}
+
+				if is_split_brain {
+					output.extend_from_slice(b" \x1B[1;31mSPLIT-BRAIN: both nodes are MASTER\x1B[0m");
+				}
+			}
+
+			if let Some(pfsync) = &high_availability.pfsync {
+				write!(
+					output,
+					"\n\x1B[K                {:>max_carp_label_len$} : \x1B[{}m{:13}\x1B[0m syncdev {} syncpeer {}",
+					"pfsync",
+					get_color_for_up_down(pfsync.syncok),
+					if pfsync.syncok { "in sync" } else { "bulk updating" },
+					pfsync.syncdev.as_deref().unwrap_or("none"),
+					pfsync.syncpeer.as_deref().unwrap_or("multicast"),
+					max_carp_label_len = max_carp_label_len,
+				)?;
+			}
+
+			if let Some((peer_hostname, peer_error)) = peer_error {
+				write!(
+					output,
+					"\n\x1B[K                {:>max_carp_label_len$} : \x1B[0;31m{}\x1B[0m",
+					peer_hostname,
+					peer_error,
+					max_carp_label_len = max_carp_label_len,
+				)?;
+			}
+		}
+
+
+		if !vpn_tunnels.is_empty() {
+			output.extend_from_slice(b"\n\x1B[K");
+
+			output.extend_from_slice(b"\n\x1B[KVPN           : ");
+
+			for (i, tunnel) in vpn_tunnels.iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				let clients = match tunnel.clients {
+					Some(1) => "1 client".to_owned(),
+					Some(clients) => format!("{} clients", clients),
+					None => String::new(),
+				};
+
+				let latest_handshake = match tunnel.latest_handshake {
+					Some(latest_handshake) => format!("{} {} ago", tunnel.latest_handshake_kind(), HumanDuration(latest_handshake)),
+					None => String::new(),
+				};
+
+				write!(
+					output,
+					"{:>max_vpn_tunnel_description_len$} : \x1B[{}m{:4}\x1B[0m {:14} {:11} {:24} ",
+					tunnel.description(),
+					get_color_for_up_down(tunnel.is_up),
+					if tunnel.is_up { "up" } else { "down" },
+					tunnel.kind(),
+					clients,
+					latest_handshake,
+					max_vpn_tunnel_description_len = max_vpn_tunnel_description_len,
+				)?;
+
+				match tunnel.speed(time_since_previous) {
+					Some((received_speed, sent_speed)) =>
+						write!(output, "{}b/s down {}b/s up", HumanSizeBase10(received_speed), HumanSizeBase10(sent_speed))?,
+
+					None if tunnel.is_up =>
+						output.extend_from_slice(b"    ?  b/s down     ?  b/s up"),
+
+					None => (),
+				}
+			}
+		}
+
+
+		for &(title, queues) in &[("Shaper queues : ", shaper.altq_queues()), ("Limiters      : ", shaper.limiters())] {
+			if queues.is_empty() {
+				continue;
+			}
+
+			write!(output, "\n\x1B[K\n\x1B[K{}", title)?;
+
+			for (i, queue) in queues.iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				write!(output, "{:>max_shaper_queue_label_len$} : ", queue.label, max_shaper_queue_label_len = max_shaper_queue_label_len)?;
+
+				if !queue.is_loaded {
+					output.extend_from_slice(b"\x1B[0;31mnot loaded\x1B[0m");
+					continue;
+				}
+
+				match queue.rates(time_since_previous) {
+					Some((bit_rate, packet_rate, drop_rate)) => write!(
+						output,
+						"{}b/s {}p/s \x1B[{}m{:9} dropped {}p/s\x1B[0m ",
+						HumanSizeBase10(bit_rate),
+						HumanSizeBase10(packet_rate),
+						// Drops that are still increasing mean the queue is currently over its limit.
+						if drop_rate > 0. { "0;31" } else if queue.dropped_packets() > 0 { "1;33" } else { "1;32" },
+						queue.dropped_packets(),
+						HumanSizeBase10(drop_rate),
+					)?,
+
+					None => write!(output, "    ?  b/s     ?  p/s {:9} dropped     ?  p/s ", queue.dropped_packets())?,
+				}
+
+				match queue.limit {
+					Some(limit) => write!(output, "queued {:4} / {:4}", queue.queued_packets(), limit)?,
+					None => write!(output, "queued {:4}", queue.queued_packets())?,
+				}
+			}
+		}
+
+
+		if let Some(dhcp_leases) = &dhcp_leases {
+			let hosts = dhcp_leases.hosts();
+
+			output.extend_from_slice(b"\n\x1B[K");
+
+			output.extend_from_slice(b"\n\x1B[KDHCP leases   : ");
+
+			let max_hostname_len = hosts.iter().map(|host| host.hostname.as_deref().unwrap_or("?").len()).max().unwrap_or_default();
+			let max_host_interface_len =
+				hosts.iter()
+				.map(|host| host.interface.as_deref().map_or(0, |interface| interface_labels.get(interface).len()))
+				.max().unwrap_or_default();
+
+			for (i, host) in hosts.iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				let state = match &host.state {
+					dhcp::State::Static => "static".to_owned(),
+					dhcp::State::Active { expires_in: Some(expires_in) } => format!("expires in {}", HumanDuration(*expires_in)),
+					dhcp::State::Active { expires_in: None } => "active".to_owned(),
+					dhcp::State::Inactive(binding_state) => binding_state.clone(),
+				};
+
+				write!(
+					output,
+					"{:>max_hostname_len$} : {:17} {:15} {:20} \x1B[{}m{:7}\x1B[0m {:max_host_interface_len$}",
+					host.hostname.as_deref().unwrap_or("?"),
+					host.mac_address.as_deref().unwrap_or("?"),
+					host.address.as_deref().unwrap_or(""),
+					state,
+					get_color_for_up_down(host.is_online),
+					if host.is_online { "online" } else { "offline" },
+					host.interface.as_deref().map_or("", |interface| interface_labels.get(interface)),
+					max_hostname_len = max_hostname_len,
+					max_host_interface_len = max_host_interface_len,
+				)?;
+
+				for ipv6_address in &host.ipv6_addresses {
+					write!(output, " {}", ipv6_address)?;
+				}
+			}
+		}
+
+
+		if let Some(firewall_rules) = &firewall_rules {
+			output.extend_from_slice(b"\n\x1B[K");
+
+			output.extend_from_slice(b"\n\x1B[KRules         : ");
+
+			let max_rule_label_len = firewall_rules.rules().iter().map(|rule| rule.label.len()).max().unwrap_or_default();
+
+			for (i, rule) in firewall_rules.rules().iter().enumerate() {
+				if i > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+
+				write!(output, "{:>max_rule_label_len$} : ", rule.label, max_rule_label_len = max_rule_label_len)?;
+
+				match rule.rates(time_since_previous) {
+					Some((evaluation_rate, packet_rate, bit_rate)) => write!(
+						output,
+						"{}b/s {}p/s {} evals/s ",
+						HumanSizeBase10(bit_rate),
+						HumanSizeBase10(packet_rate),
+						HumanSizeBase10(evaluation_rate),
 					)?,
+
+					None => output.extend_from_slice(b"    ?  b/s     ?  p/s     ?   evals/s "),
+				}
+
+				write!(output, "{:6} states", rule.states)?;
+			}
+
+			if !firewall_rules.tables().is_empty() {
+				output.extend_from_slice(b"\n\x1B[K");
+
+				output.extend_from_slice(b"\n\x1B[KTables        : ");
+
+				let max_table_name_len = firewall_rules.tables().iter().map(|table| table.name.len()).max().unwrap_or_default();
+
+				for (i, table) in firewall_rules.tables().iter().enumerate() {
+					if i > 0 {
+						output.extend_from_slice(b"\n\x1B[K                ");
+					}
+
+					write!(output, "{:>max_table_name_len$} : {:8} addresses ", table.name, table.addresses, max_table_name_len = max_table_name_len)?;
+
+					match table.match_rate(time_since_previous) {
+						Some(match_rate) => write!(output, "{} matches/s ", HumanSizeBase10(match_rate))?,
+						None => output.extend_from_slice(b"    ?   matches/s "),
+					}
+
+					write!(output, "{:10} blocked {:10} passed", table.blocked_packets, table.passed_packets)?;
+
+					if let Some(description) = &table.description {
+						write!(output, " {}", description)?;
+					}
 				}
 			}
 		}
@@ -421,11 +1316,87 @@ fn main() -> Result<(), Error> {
 		{
 			output.extend_from_slice(b"\n\x1B[KFirewall logs : ");
 
-			let firewall_logs = firewall_logs.lock().expect("could not lock firewall logs queue");
-			for (i, firewall_log) in firewall_logs.iter().enumerate() {
-				if i > 0 {
+			// The logs fill the rest of the terminal. The output starts at row 5, and this section starts on the current row.
+			#[allow(clippy::naive_bytecount)] // output is only a few KiB
+			let current_row = 5 + output.iter().filter(|&&b| b == b'\n').count();
+			let num_rows = match tty::num_rows() {
+				Some(num_terminal_rows) => (num_terminal_rows + 1).saturating_sub(current_row).max(1),
+				None => 10,
+			};
+			let num_rows = firewall_logs_config.max_rows.map_or(num_rows, |max_rows| num_rows.min(max_rows));
+
+			let mut firewall_logs = firewall_logs.lock().expect("could not lock firewall logs queue");
+
+			let mut num_rows_written = 0;
+
+			for alert in firewall_logs.alerts().into_iter().take(num_rows.saturating_sub(1)) {
+				if num_rows_written > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+				num_rows_written += 1;
+
+				match alert.kind {
+					scan_detection::AlertKind::PortScan => write!(
+						output,
+						"\x1B[1;31mport scan from {} on {}: {} ports in {} s\x1B[0m",
+						alert.source,
+						interface_labels.get(&alert.interface),
+						alert.count,
+						alert.window.as_secs(),
+					)?,
+
+					scan_detection::AlertKind::BruteForce { port } => write!(
+						output,
+						"\x1B[1;31mbrute force from {} on {}: {} attempts on port {} in {} s\x1B[0m",
+						alert.source,
+						interface_labels.get(&alert.interface),
+						alert.count,
+						port,
+						alert.window.as_secs(),
+					)?,
+				}
+			}
+
+			if let Some(archive_error) = firewall_logs.archive_error() {
+				if num_rows_written > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+				num_rows_written += 1;
+
+				write!(output, "\x1B[1;31m{}\x1B[0m", archive_error)?;
+			}
+
+			let search = firewall_logs.search();
+			let scroll_offset = firewall_logs.scroll_offset();
+			if search.is_some() || scroll_offset > 0 {
+				if num_rows_written > 0 {
+					output.extend_from_slice(b"\n\x1B[K                ");
+				}
+				num_rows_written += 1;
+
+				output.extend_from_slice(b"\x1B[7m");
+
+				if let Some((search, is_editing_search)) = search {
+					write!(output, "/{}{} ({} matching)", search, if is_editing_search { "_" } else { "" }, firewall_logs.num_matching())?;
+				}
+
+				if scroll_offset > 0 {
+					if search.is_some() {
+						output.extend_from_slice(b", ");
+					}
+					write!(output, "{} newer not shown", scroll_offset)?;
+				}
+
+				output.extend_from_slice(b"\x1B[0m");
+			}
+
+			firewall_logs.set_page_len(num_rows.saturating_sub(num_rows_written));
+
+			for firewall_log in firewall_logs.iter().take(num_rows.saturating_sub(num_rows_written)) {
+				if num_rows_written > 0 {
 					output.extend_from_slice(b"\n\x1B[K                ");
 				}
+				num_rows_written += 1;
 
 				let firewall_log_color = get_color_for_up_down(match firewall_log.action {
 					firewall_logs::Action::Block => true,
@@ -435,10 +1406,10 @@ fn main() -> Result<(), Error> {
 				match firewall_log.protocol {
 					firewall_logs::Protocol::Icmp { source, destination: _ } => write!(
 						output,
-						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {}      icmp <- {}\x1B[0m",
+						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {}      icmp <- {}",
 						firewall_log_color,
 						firewall_log.timestamp,
-						firewall_log.interface,
+						interface_labels.get(&firewall_log.interface),
 						firewall_log.action,
 						source,
 						max_firewall_log_interface_name_len = max_firewall_log_interface_name_len,
@@ -446,10 +1417,10 @@ fn main() -> Result<(), Error> {
 
 					firewall_logs::Protocol::Tcp { source, destination } => write!(
 						output,
-						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {} {:5}/tcp <- {}\x1B[0m",
+						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {} {:5}/tcp <- {}",
 						firewall_log_color,
 						firewall_log.timestamp,
-						firewall_log.interface,
+						interface_labels.get(&firewall_log.interface),
 						firewall_log.action,
 						destination.port(),
 						source.ip(),
@@ -458,20 +1429,37 @@ fn main() -> Result<(), Error> {
 
 					firewall_logs::Protocol::Udp { source, destination } => write!(
 						output,
-						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {} {:5}/udp <- {}\x1B[0m",
+						"\x1B[{}m{} {:max_firewall_log_interface_name_len$} {} {:5}/udp <- {}",
 						firewall_log_color,
 						firewall_log.timestamp,
-						firewall_log.interface,
+						interface_labels.get(&firewall_log.interface),
 						firewall_log.action,
 						destination.port(),
 						source.ip(),
 						max_firewall_log_interface_name_len = max_firewall_log_interface_name_len,
 					)?,
 				};
+
+				if let Some(reverse_dns) = &reverse_dns {
+					let source = match firewall_log.protocol {
+						firewall_logs::Protocol::Icmp { source, destination: _ } => source,
+						firewall_logs::Protocol::Tcp { source, destination: _ } |
+						firewall_logs::Protocol::Udp { source, destination: _ } => source.ip(),
+					};
+					if let Some(source_name) = reverse_dns.lookup(source) {
+						write!(output, " ({})", source_name)?;
+					}
+				}
+
+				output.extend_from_slice(b"\x1B[0m");
 			}
 		}
 
 
+		// The number of firewall log rows varies, so clear whatever was left below them by the previous iteration.
+		output.extend_from_slice(b"\x1B[J");
+
+
 		stdout.write_all(&output)?;
 		stdout.flush()?;
 		output.clear();
@@ -557,6 +1545,31 @@ macro_rules! impl_parse {
 impl_parse! { u32 }
 impl_parse! { u64 }
 
+const MEMORY_BAR_LEN: u64 = 50;
+
+/// The rate of change of a counter. Counters can go backwards, such as when they're reset, so that isn't shown as a negative rate.
+fn per_second(current: u64, previous: u64, time_since_previous: std::time::Duration) -> f32 {
+	current.saturating_sub(previous) as f32 / time_since_previous.as_secs_f32()
+}
+
+/// Writes the nonzero counters of problems like drops or failures, or `none` if there aren't any.
+///
+/// Counters that have increased since the previous iteration are still increasing, so they're shown in red rather than yellow.
+fn write_counters<'a>(output: &mut Vec<u8>, counters: impl IntoIterator<Item = (&'a str, u64, bool)>, none: &str) -> Result<(), Error> {
+	let mut num_nonzero_counters = 0;
+	for (name, value, is_increasing) in counters {
+		if value > 0 {
+			write!(output, " \x1B[{}m{} {}\x1B[0m", if is_increasing { "0;31" } else { "1;33" }, name, value)?;
+			num_nonzero_counters += 1;
+		}
+	}
+	if num_nonzero_counters == 0 {
+		write!(output, " \x1B[1;32m{}\x1B[0m", none)?;
+	}
+
+	Ok(())
+}
+
 fn usage(used: f32, max: f32) -> (f32, &'static str) {
 	let usage_percent = used * 100. / max;
 	let usage_color = get_color_for_usage(usage_percent);
@@ -575,6 +1588,22 @@ fn get_color_for_temperature(temp: f32) -> &'static str {
 	}
 }
 
+/// High interrupt time usually means a NIC is misbehaving.
+fn is_interrupt_high(interrupt_percent: f32) -> bool {
+	interrupt_percent >= 10.
+}
+
+/// The label of an interface in the interfaces section, with a VLAN that's shown under its parent marked as such.
+fn interface_label(interface_labels: &interface::Labels, name: &str, is_nested: bool) -> String {
+	let label = interface_labels.get(name);
+	if is_nested {
+		format!("\u{2514} {}", label)
+	}
+	else {
+		label.to_owned()
+	}
+}
+
 fn get_color_for_up_down(is_up: bool) -> &'static str {
 	if is_up {
 		"1;32"
@@ -584,6 +1613,18 @@ fn get_color_for_up_down(is_up: bool) -> &'static str {
 	}
 }
 
+fn get_color_for_thresholds(usage: f32, thresholds: config::UsageThresholds) -> &'static str {
+	if usage >= thresholds.critical {
+		"0;31"
+	}
+	else if usage >= thresholds.warning {
+		"1;33"
+	}
+	else {
+		"1;32"
+	}
+}
+
 fn get_color_for_usage(usage: f32) -> &'static str {
 	match usage {
 		usage if usage < 5. => "0;34",
@@ -625,3 +1666,25 @@ impl std::fmt::Display for HumanSizeBase10 {
 		write!(f, "{:5.1} T", value)
 	}
 }
+
+/// Formats a duration with its two most significant units, like `42s`, `5m 07s` or `3d 04h`
+#[derive(Clone, Copy, Debug)]
+struct HumanDuration(std::time::Duration);
+
+impl std::fmt::Display for HumanDuration {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let value = self.0.as_secs();
+		if value < 60 {
+			write!(f, "{}s", value)
+		}
+		else if value < 60 * 60 {
+			write!(f, "{}m {:02}s", value / 60, value % 60)
+		}
+		else if value < 24 * 60 * 60 {
+			write!(f, "{}h {:02}m", value / (60 * 60), (value % (60 * 60)) / 60)
+		}
+		else {
+			write!(f, "{}d {:02}h", value / (24 * 60 * 60), (value % (24 * 60 * 60)) / (60 * 60))
+		}
+	}
+}
@@ -2,27 +2,80 @@
 pub(crate) struct Memory {
 	pub(crate) physical: crate::c_ulong,
 	pub(crate) num_pages: crate::c_uint,
+	pub(crate) page_size: crate::c_uint,
 	pub(crate) used_pages: crate::c_uint,
+
+	pub(crate) wired_pages: crate::c_uint,
+	pub(crate) active_pages: crate::c_uint,
+	pub(crate) inactive_pages: crate::c_uint,
+	pub(crate) laundry_pages: crate::c_uint,
+	pub(crate) free_pages: crate::c_uint,
+
+	// ZFS ARC is part of wired memory.
+	pub(crate) arc_bytes: u64,
+
+	// vm.stats.vm.v_laundry_count only exists on FreeBSD 12 and above.
+	pub(crate) has_laundry: bool,
+
+	// kstat.zfs.misc.arcstats.size only exists if ZFS is loaded.
+	pub(crate) has_arc: bool,
 }
 
 impl Memory {
+	pub(crate) fn probe(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		self.has_laundry = crate::ssh_exec::sysctl_N::exists("vm.stats.vm.v_laundry_count", session)?;
+		self.has_arc = crate::ssh_exec::sysctl_N::exists("kstat.zfs.misc.arcstats.size", session)?;
+		Ok(())
+	}
+
 	pub(crate) fn update(&mut self, reader: &mut impl std::io::Read) -> Result<(), crate::Error> {
 		let inactive_pages: crate::c_uint = crate::Parse::parse(reader)?;
 		let cache_pages: crate::c_uint = crate::Parse::parse(reader)?;
 		let free_pages: crate::c_uint = crate::Parse::parse(reader)?;
 		self.used_pages = self.num_pages - inactive_pages - cache_pages - free_pages;
+
+		self.wired_pages = crate::Parse::parse(reader)?;
+		self.active_pages = crate::Parse::parse(reader)?;
+		self.inactive_pages = inactive_pages;
+		self.free_pages = cache_pages + free_pages;
+
+		if self.has_laundry {
+			self.laundry_pages = crate::Parse::parse(reader)?;
+		}
+
+		if self.has_arc {
+			self.arc_bytes = crate::Parse::parse(reader)?;
+		}
+
 		Ok(())
 	}
+
+	pub(crate) fn pages_to_mib(&self, pages: crate::c_uint) -> u64 {
+		u64::from(pages) * u64::from(self.page_size) / 1_048_576
+	}
 }
 
 impl crate::Parse for Memory {
 	fn parse<R>(reader: &mut R) -> std::io::Result<Self> where R: std::io::Read {
 		let physical = crate::Parse::parse(reader)?;
 		let num_pages = crate::Parse::parse(reader)?;
+		let page_size = crate::Parse::parse(reader)?;
 		Ok(crate::memory::Memory {
 			physical,
 			num_pages,
+			page_size,
 			used_pages: 0,
+
+			wired_pages: 0,
+			active_pages: 0,
+			inactive_pages: 0,
+			laundry_pages: 0,
+			free_pages: 0,
+
+			arc_bytes: 0,
+
+			has_laundry: false,
+			has_arc: false,
 		})
 	}
 }
@@ -3,13 +3,152 @@ pub(crate) struct PfConfig {
 	pub(crate) gateway_interfaces: std::collections::BTreeSet<String>,
 	pub(crate) bridge_interfaces: Vec<String>,
 	pub(crate) other_interfaces: Vec<String>,
+
+	/// Map of interface device name (like `igb1`) to its description (like `LAN`)
+	pub(crate) interface_descriptions: std::collections::BTreeMap<String, String>,
+
+	/// Map of interface device name to how it's built on top of other interfaces, for VLAN, LAGG and PPP interfaces
+	pub(crate) interface_topologies: std::collections::BTreeMap<String, Topology>,
+
 	pub(crate) gateways: Vec<Gateway>,
+	pub(crate) gateway_groups: Vec<GatewayGroup>,
+
+	/// Names of the gateways or gateway groups that are configured as the IPv4 and IPv6 default gateways, if any
+	pub(crate) default_gateway_v4: Option<String>,
+	pub(crate) default_gateway_v6: Option<String>,
+
+	pub(crate) vpn_tunnels: Vec<VpnTunnel>,
+	pub(crate) dhcp_static_mappings: Vec<DhcpStaticMapping>,
+
+	pub(crate) carp_vips: Vec<CarpVip>,
+	pub(crate) pfsync_enabled: bool,
+
+	pub(crate) shaper_queues: Vec<ShaperQueue>,
+	pub(crate) limiters: Vec<Limiter>,
+
+	/// Map of filter rule tracker ID, which pfSense sets as the rule's `ridentifier` in pf, to the rule's description
+	pub(crate) rule_descriptions: std::collections::BTreeMap<u32, String>,
+
+	pub(crate) table_aliases: Vec<TableAlias>,
+
+	/// Whether the DNS resolver (unbound) is enabled
+	pub(crate) dns_resolver_enabled: bool,
+
 	pub(crate) services: Vec<Service>,
 }
 
 #[derive(Debug)]
 pub(crate) struct Gateway {
 	pub(crate) name: String,
+
+	/// The device name of the gateway's interface
+	pub(crate) interface: String,
+
+	/// `None` for dynamic gateways
+	pub(crate) address: Option<String>,
+
+	pub(crate) is_ipv6: bool,
+	pub(crate) monitor: Option<String>,
+	pub(crate) monitor_disabled: bool,
+	pub(crate) latency_low: std::time::Duration,
+	pub(crate) latency_high: std::time::Duration,
+	pub(crate) loss_low: u64,
+	pub(crate) loss_high: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct GatewayGroup {
+	pub(crate) name: String,
+
+	/// Gateway names and their tiers
+	pub(crate) members: Vec<(String, u32)>,
+
+	pub(crate) trigger: GatewayGroupTrigger,
+}
+
+/// The conditions that cause a gateway group member to be considered down.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GatewayGroupTrigger {
+	pub(crate) on_loss: bool,
+	pub(crate) on_latency: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Topology {
+	Vlan { parent: String, tag: u16 },
+	Lagg { members: Vec<String>, protocol: String },
+	Ppp { r#type: String, ports: Vec<String> },
+}
+
+#[derive(Debug)]
+pub(crate) struct VpnTunnel {
+	pub(crate) kind: VpnKind,
+
+	// The name that OpenVPN, strongSwan or WireGuard knows the tunnel by, like `server1`, `con1` or `tun_wg0`
+	pub(crate) id: String,
+
+	pub(crate) description: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum VpnKind {
+	OpenVpnServer,
+	OpenVpnClient,
+	Ipsec,
+	WireGuard,
+}
+
+#[derive(Debug)]
+pub(crate) struct DhcpStaticMapping {
+	/// The device name of the interface whose DHCP server has this mapping
+	pub(crate) interface: String,
+
+	/// Lowercased, to match the leases file and the ARP table
+	pub(crate) mac_address: String,
+
+	/// `None` if the mapping only sets other options, like the hostname
+	pub(crate) address: Option<String>,
+
+	pub(crate) hostname: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CarpVip {
+	/// The device name of the VIP's interface
+	pub(crate) interface: String,
+
+	pub(crate) vhid: u8,
+	pub(crate) address: String,
+	pub(crate) description: Option<String>,
+}
+
+/// An ALTQ queue
+#[derive(Debug)]
+pub(crate) struct ShaperQueue {
+	/// The device name of the queue's interface
+	pub(crate) interface: String,
+
+	/// The name that pfctl knows the queue by. The root queue of each interface is named like `root_em0`
+	pub(crate) name: String,
+
+	pub(crate) description: Option<String>,
+}
+
+/// A dummynet pipe
+#[derive(Debug)]
+pub(crate) struct Limiter {
+	pub(crate) name: String,
+	pub(crate) number: u32,
+
+	/// Names and dummynet queue numbers of the limiter's child queues
+	pub(crate) queues: Vec<(String, u32)>,
+}
+
+/// An alias that pfSense loads into a pf table of the same name. Port aliases aren't included since they aren't tables.
+#[derive(Debug)]
+pub(crate) struct TableAlias {
+	pub(crate) name: String,
+	pub(crate) description: Option<String>,
 }
 
 #[derive(Debug)]
@@ -24,30 +163,161 @@ impl PfConfig {
 		let pfconfig = roxmltree::Document::parse(&pfconfig)?;
 		let mut pfconfig: PfSense<'_> = std::convert::TryInto::try_into(pfconfig.root_element())?;
 
+		// Disabled interfaces have no addresses and pass no traffic, so there's nothing to show for them.
+		let disabled_interfaces: std::collections::BTreeSet<_> =
+			pfconfig.interfaces.0.values()
+			.filter(|interface| !interface.enable)
+			.map(|interface| interface.r#if)
+			.collect();
+
+		let interface_descriptions =
+			pfconfig.interfaces.0.iter()
+			.filter(|(_, interface)| interface.enable)
+			.map(|(&name, interface)| {
+				// pfSense uses the uppercased name, like `WAN` or `OPT1`, for interfaces that don't have a description.
+				let description = interface.descr.map_or_else(|| name.to_uppercase(), ToOwned::to_owned);
+				(interface.r#if.to_owned(), description)
+			})
+			.collect();
+
+		let mut dhcp_static_mappings = vec![];
+
+		if let Some(dhcpd) = pfconfig.dhcpd {
+			for (interface_name, DhcpStaticMap { mac, ipaddr, hostname, descr }) in dhcpd.0 {
+				// pfSense leaves the DHCP server config behind when its interface is deleted
+				let Some(interface) = pfconfig.interfaces.0.get(interface_name) else {
+					continue;
+				};
+				if !interface.enable {
+					continue;
+				}
+
+				// Mappings by client identifier can't be matched to the ARP table
+				let Some(mac) = mac else {
+					continue;
+				};
+
+				dhcp_static_mappings.push(DhcpStaticMapping {
+					interface: interface.r#if.to_owned(),
+					mac_address: mac.to_lowercase(),
+					address: ipaddr.map(ToOwned::to_owned),
+					hostname: hostname.or(descr).map(ToOwned::to_owned),
+				});
+			}
+		}
+
+		let mut carp_vips = vec![];
+
+		if let Some(virtual_ips) = pfconfig.virtual_ips {
+			for Vip { interface, vhid, subnet, descr } in virtual_ips.0 {
+				// VIPs can also be on other VIPs, like `_vip5f3a1b2c4d5e6`, which aren't CARP interfaces themselves.
+				let Some(interface) = pfconfig.interfaces.0.get(interface) else {
+					continue;
+				};
+				if !interface.enable {
+					continue;
+				}
+
+				carp_vips.push(CarpVip {
+					interface: interface.r#if.to_owned(),
+					vhid,
+					address: subnet.to_owned(),
+					description: descr.map(ToOwned::to_owned),
+				});
+			}
+		}
+
+		let mut shaper_queues = vec![];
+
+		if let Some(shaper) = pfconfig.shaper {
+			for (interface_name, name, description) in shaper.0 {
+				let Some(interface) = pfconfig.interfaces.0.get(interface_name) else {
+					continue;
+				};
+				if !interface.enable {
+					continue;
+				}
+
+				// The top-level queue of each interface is its root queue, which is named after the interface in config.xml.
+				let name = name.map_or_else(|| format!("root_{}", interface.r#if), ToOwned::to_owned);
+
+				shaper_queues.push(ShaperQueue {
+					interface: interface.r#if.to_owned(),
+					name,
+					description: description.map(ToOwned::to_owned),
+				});
+			}
+		}
+
+		let limiters =
+			pfconfig.dnshaper.into_iter().flat_map(|dnshaper| dnshaper.0)
+			.map(|DnShaperQueue { name, number, queues }| Limiter {
+				name: name.to_owned(),
+				number,
+				queues: queues.into_iter().map(|DnShaperQueue { name, number, .. }| (name.to_owned(), number)).collect(),
+			})
+			.collect();
+
 		let mut gateway_interfaces: std::collections::BTreeSet<_> = Default::default();
 		let mut gateways = vec![];
 
-		for (&gateway_name, &gateway_interface) in &pfconfig.gateways.0 {
-			let &r#if =
+		for (&gateway_name, gateway_item) in &pfconfig.gateways.items {
+			let &Interface { r#if, enable, .. } =
 				pfconfig.interfaces.0
-				.get(gateway_interface)
-				.ok_or_else(|| format!("gateway {} is defined on interface {} but this interface does not exist", gateway_name, gateway_interface))?;
-			gateway_interfaces.insert(r#if.to_owned());
+				.get(gateway_item.interface)
+				.ok_or_else(|| format!("gateway {} is defined on interface {} but this interface does not exist", gateway_name, gateway_item.interface))?;
+			if enable {
+				gateway_interfaces.insert(r#if.to_owned());
+			}
+
+			if gateway_item.disabled {
+				continue;
+			}
 
+			// Defaults are the same as pfSense's
 			gateways.push(Gateway {
 				name: gateway_name.to_owned(),
+				interface: r#if.to_owned(),
+				address: gateway_item.gateway.filter(|&address| address != "dynamic").map(ToOwned::to_owned),
+				is_ipv6: gateway_item.ipprotocol == Some("inet6"),
+				monitor: gateway_item.monitor.map(ToOwned::to_owned),
+				monitor_disabled: gateway_item.monitor_disable,
+				latency_low: std::time::Duration::from_millis(gateway_item.latencylow.unwrap_or(200)),
+				latency_high: std::time::Duration::from_millis(gateway_item.latencyhigh.unwrap_or(500)),
+				loss_low: gateway_item.losslow.unwrap_or(10),
+				loss_high: gateway_item.losshigh.unwrap_or(20),
 			});
 		}
 
-		for (_, gateway_interface) in pfconfig.gateways.0 {
-			let _ = pfconfig.interfaces.0.remove(gateway_interface);
+		for gateway_item in pfconfig.gateways.items.values() {
+			let _ = pfconfig.interfaces.0.remove(gateway_item.interface);
 		}
 
-		let mut interfaces: std::collections::BTreeSet<_> = pfconfig.interfaces.0.into_iter().map(|(_, interface_if)| interface_if).collect();
+		let gateway_groups =
+			pfconfig.gateways.groups.into_iter()
+			.map(|GatewayGroupItem { name, members, trigger }| GatewayGroup {
+				name: name.to_owned(),
+				members: members.into_iter().map(|(gateway_name, tier)| (gateway_name.to_owned(), tier)).collect(),
+				trigger: GatewayGroupTrigger {
+					on_loss: trigger.contains("loss"),
+					on_latency: trigger.contains("latency"),
+				},
+			})
+			.collect();
+
+		let mut interfaces: std::collections::BTreeSet<_> =
+			pfconfig.interfaces.0.into_values()
+			.filter(|interface| interface.enable)
+			.map(|Interface { r#if, .. }| r#if)
+			.collect();
 		let mut bridge_interfaces = vec![];
 
 		if let Some(bridges) = pfconfig.bridges {
 			for bridge_name in bridges.0 {
+				if disabled_interfaces.contains(bridge_name) {
+					continue;
+				}
+
 				bridge_interfaces.push(bridge_name.to_owned());
 
 				if !interfaces.remove(bridge_name) {
@@ -58,6 +328,48 @@ impl PfConfig {
 
 		let other_interfaces = interfaces.into_iter().map(ToOwned::to_owned).collect();
 
+		let mut interface_topologies: std::collections::BTreeMap<_, _> = Default::default();
+
+		if let Some(vlans) = pfconfig.vlans {
+			for Vlan { r#if, tag, vlanif } in vlans.0 {
+				let _ = interface_topologies.insert(vlanif, Topology::Vlan { parent: r#if.to_owned(), tag });
+			}
+		}
+
+		if let Some(laggs) = pfconfig.laggs {
+			for Lagg { members, laggif, proto } in laggs.0 {
+				let members = members.split(',').filter(|member| !member.is_empty()).map(ToOwned::to_owned).collect();
+				let _ = interface_topologies.insert(laggif.to_owned(), Topology::Lagg { members, protocol: proto.to_owned() });
+			}
+		}
+
+		if let Some(ppps) = pfconfig.ppps {
+			for Ppp { r#if, r#type, ports } in ppps.0 {
+				let ports = ports.split(',').filter(|port| !port.is_empty()).map(ToOwned::to_owned).collect();
+				let _ = interface_topologies.insert(r#if.to_owned(), Topology::Ppp { r#type: r#type.to_owned(), ports });
+			}
+		}
+
+		let vpn_tunnels =
+			pfconfig.openvpn.into_iter().flat_map(|openvpn| openvpn.0)
+			.chain(pfconfig.ipsec.into_iter().flat_map(|ipsec| ipsec.0))
+			.chain(pfconfig.wireguard.into_iter().flat_map(|wireguard| wireguard.0))
+			.collect();
+
+		let rule_descriptions =
+			pfconfig.filter.into_iter().flat_map(|filter| filter.0)
+			.map(|(tracker, descr)| (tracker, descr.to_owned()))
+			.collect();
+
+		let table_aliases =
+			pfconfig.aliases.into_iter().flat_map(|aliases| aliases.0)
+			.filter(|alias| !alias.r#type.contains("port"))
+			.map(|Alias { name, r#type: _, descr }| TableAlias {
+				name: name.to_owned(),
+				description: descr.map(ToOwned::to_owned),
+			})
+			.collect();
+
 		let services =
 			pfconfig.installed_packages.0.into_iter()
 			.map(|(name, executable)| Service {
@@ -70,7 +382,21 @@ impl PfConfig {
 			gateway_interfaces,
 			bridge_interfaces,
 			other_interfaces,
+			interface_descriptions,
+			interface_topologies,
 			gateways,
+			gateway_groups,
+			default_gateway_v4: pfconfig.gateways.defaultgw4.map(ToOwned::to_owned),
+			default_gateway_v6: pfconfig.gateways.defaultgw6.map(ToOwned::to_owned),
+			vpn_tunnels,
+			dhcp_static_mappings,
+			carp_vips,
+			pfsync_enabled: pfconfig.hasync.is_some_and(|hasync| hasync.pfsyncenabled),
+			shaper_queues,
+			limiters,
+			rule_descriptions,
+			table_aliases,
+			dns_resolver_enabled: pfconfig.unbound.is_some_and(|unbound| unbound.enable),
 			services,
 		};
 
@@ -82,8 +408,22 @@ impl PfConfig {
 struct PfSense<'input> {
 	bridges: Option<Bridges<'input>>,
 	interfaces: Interfaces<'input>,
+	vlans: Option<Vlans<'input>>,
+	laggs: Option<Laggs<'input>>,
+	ppps: Option<Ppps<'input>>,
 	gateways: Gateways<'input>,
+	dhcpd: Option<Dhcpd<'input>>,
+	virtual_ips: Option<VirtualIps<'input>>,
+	hasync: Option<HaSync>,
+	shaper: Option<Shaper<'input>>,
+	dnshaper: Option<DnShaper<'input>>,
+	filter: Option<Filter<'input>>,
+	aliases: Option<Aliases<'input>>,
+	unbound: Option<Unbound>,
+	openvpn: Option<OpenVpn>,
+	ipsec: Option<Ipsec>,
 	installed_packages: InstalledPackages<'input>,
+	wireguard: Option<WireGuard>,
 }
 
 impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for PfSense<'input> {
@@ -92,13 +432,41 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for PfSense<
 	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
 		let bridges_tag_name: roxmltree::ExpandedName<'_, '_> = "bridges".into();
 		let interfaces_tag_name: roxmltree::ExpandedName<'_, '_> = "interfaces".into();
+		let vlans_tag_name: roxmltree::ExpandedName<'_, '_> = "vlans".into();
+		let laggs_tag_name: roxmltree::ExpandedName<'_, '_> = "laggs".into();
+		let ppps_tag_name: roxmltree::ExpandedName<'_, '_> = "ppps".into();
 		let gateways_tag_name: roxmltree::ExpandedName<'_, '_> = "gateways".into();
+		let dhcpd_tag_name: roxmltree::ExpandedName<'_, '_> = "dhcpd".into();
+		let virtual_ips_tag_name: roxmltree::ExpandedName<'_, '_> = "virtualip".into();
+		let hasync_tag_name: roxmltree::ExpandedName<'_, '_> = "hasync".into();
+		let shaper_tag_name: roxmltree::ExpandedName<'_, '_> = "shaper".into();
+		let dnshaper_tag_name: roxmltree::ExpandedName<'_, '_> = "dnshaper".into();
+		let filter_tag_name: roxmltree::ExpandedName<'_, '_> = "filter".into();
+		let aliases_tag_name: roxmltree::ExpandedName<'_, '_> = "aliases".into();
+		let unbound_tag_name: roxmltree::ExpandedName<'_, '_> = "unbound".into();
+		let openvpn_tag_name: roxmltree::ExpandedName<'_, '_> = "openvpn".into();
+		let ipsec_tag_name: roxmltree::ExpandedName<'_, '_> = "ipsec".into();
 		let installed_packages_tag_name: roxmltree::ExpandedName<'_, '_> = "installedpackages".into();
+		let wireguard_tag_name: roxmltree::ExpandedName<'_, '_> = "wireguard".into();
 
 		let mut bridges = None;
 		let mut interfaces = None;
+		let mut vlans = None;
+		let mut laggs = None;
+		let mut ppps = None;
 		let mut gateways = None;
+		let mut dhcpd = None;
+		let mut virtual_ips = None;
+		let mut hasync = None;
+		let mut shaper = None;
+		let mut dnshaper = None;
+		let mut filter = None;
+		let mut aliases = None;
+		let mut unbound = None;
+		let mut openvpn = None;
+		let mut ipsec = None;
 		let mut installed_packages = None;
+		let mut wireguard = None;
 
 		for child in node.children() {
 			let child_tag_name = child.tag_name();
@@ -108,11 +476,55 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for PfSense<
 			else if child_tag_name == interfaces_tag_name {
 				interfaces = Some(std::convert::TryInto::try_into(child)?);
 			}
+			else if child_tag_name == vlans_tag_name {
+				vlans = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == laggs_tag_name {
+				laggs = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == ppps_tag_name {
+				ppps = Some(std::convert::TryInto::try_into(child)?);
+			}
 			else if child_tag_name == gateways_tag_name {
 				gateways = Some(std::convert::TryInto::try_into(child)?);
 			}
+			else if child_tag_name == dhcpd_tag_name {
+				dhcpd = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == virtual_ips_tag_name {
+				virtual_ips = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == hasync_tag_name {
+				hasync = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == shaper_tag_name {
+				shaper = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == dnshaper_tag_name {
+				dnshaper = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == filter_tag_name {
+				filter = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == aliases_tag_name {
+				aliases = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == unbound_tag_name {
+				unbound = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == openvpn_tag_name {
+				openvpn = Some(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == ipsec_tag_name {
+				ipsec = Some(std::convert::TryInto::try_into(child)?);
+			}
 			else if child_tag_name == installed_packages_tag_name {
 				installed_packages = Some(std::convert::TryInto::try_into(child)?);
+
+				// WireGuard is configured by a package
+				if let Some(child) = child.children().find(|child| child.tag_name() == wireguard_tag_name) {
+					wireguard = Some(std::convert::TryInto::try_into(child)?);
+				}
 			}
 		}
 
@@ -123,8 +535,22 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for PfSense<
 		Ok(PfSense {
 			bridges,
 			interfaces,
+			vlans,
+			laggs,
+			ppps,
 			gateways,
+			dhcpd,
+			virtual_ips,
+			hasync,
+			shaper,
+			dnshaper,
+			filter,
+			aliases,
+			unbound,
+			openvpn,
+			ipsec,
 			installed_packages,
+			wireguard,
 		})
 	}
 }
@@ -179,7 +605,7 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Bridge<'
 }
 
 #[derive(Debug)]
-struct Interfaces<'input>(std::collections::BTreeMap<&'input str, &'input str>);
+struct Interfaces<'input>(std::collections::BTreeMap<&'input str, Interface<'input>>);
 
 impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Interfaces<'input> {
 	type Error = crate::Error;
@@ -189,11 +615,11 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Interfac
 			node.children()
 			.filter_map(|child|
 				if child.is_element() {
-					let Interface { name, r#if } = match std::convert::TryInto::try_into(child) {
+					let interface: Interface<'_> = match std::convert::TryInto::try_into(child) {
 						Ok(interface) => interface,
 						Err(err) => return Some(Err(err)),
 					};
-					Some(Ok((name, r#if)))
+					Some(Ok((interface.name, interface)))
 				}
 				else {
 					None
@@ -209,6 +635,8 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Interfac
 struct Interface<'input> {
 	name: &'input str,
 	r#if: &'input str,
+	descr: Option<&'input str>,
+	enable: bool,
 }
 
 impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Interface<'input> {
@@ -216,45 +644,232 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Interfac
 
 	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
 		let if_tag_name: roxmltree::ExpandedName<'_, '_> = "if".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+		let enable_tag_name: roxmltree::ExpandedName<'_, '_> = "enable".into();
 
 		let name = node.tag_name().name();
 
 		let r#if = node.children().find(|node| node.tag_name() == if_tag_name).ok_or("interfaces.*.if not found in config.xml")?;
 		let r#if = r#if.text().ok_or("interfaces.*.if is not a text node")?;
 
+		let descr = node.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text());
+
+		let enable = node.children().any(|node| node.tag_name() == enable_tag_name);
+
 		Ok(Interface {
 			name,
 			r#if,
+			descr,
+			enable,
 		})
 	}
 }
 
 #[derive(Debug)]
-struct Gateways<'input>(std::collections::BTreeMap<&'input str, &'input str>);
+struct Vlans<'input>(Vec<Vlan<'input>>);
 
-impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Gateways<'input> {
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Vlans<'input> {
 	type Error = crate::Error;
 
 	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
-		let gateway_item_tag_name: roxmltree::ExpandedName<'_, '_> = "gateway_item".into();
+		let vlan_tag_name: roxmltree::ExpandedName<'_, '_> = "vlan".into();
 
 		let inner: Result<_, crate::Error> =
 			node.children()
-			.filter_map(|child|
-				if child.tag_name() == gateway_item_tag_name {
-					let GatewayItem { name, interface } = match std::convert::TryInto::try_into(child) {
-						Ok(gateway) => gateway,
-						Err(err) => return Some(Err(err)),
-					};
-					Some(Ok((name, interface)))
-				}
-				else {
-					None
-				})
+			.filter(|child| child.tag_name() == vlan_tag_name)
+			.map(std::convert::TryInto::try_into)
 			.collect();
 		let inner = inner?;
 
-		Ok(Gateways(inner))
+		Ok(Vlans(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Vlan<'input> {
+	r#if: &'input str,
+	tag: u16,
+	vlanif: String,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Vlan<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let if_tag_name: roxmltree::ExpandedName<'_, '_> = "if".into();
+		let tag_tag_name: roxmltree::ExpandedName<'_, '_> = "tag".into();
+		let vlanif_tag_name: roxmltree::ExpandedName<'_, '_> = "vlanif".into();
+
+		let r#if = node.children().find(|node| node.tag_name() == if_tag_name).ok_or("vlans.vlan.if not found in config.xml")?;
+		let r#if = r#if.text().ok_or("vlans.vlan.if is not a text node")?;
+
+		let tag = node.children().find(|node| node.tag_name() == tag_tag_name).ok_or("vlans.vlan.tag not found in config.xml")?;
+		let tag = tag.text().ok_or("vlans.vlan.tag is not a text node")?;
+		let tag = tag.parse().map_err(|err| format!("vlans.vlan.tag is not a valid VLAN tag: {}", err))?;
+
+		// Old configs don't have the vlanif, but it always has this form anyway.
+		let vlanif =
+			node.children().find(|node| node.tag_name() == vlanif_tag_name).and_then(|vlanif| vlanif.text())
+			.map_or_else(|| format!("{}.{}", r#if, tag), ToOwned::to_owned);
+
+		Ok(Vlan {
+			r#if,
+			tag,
+			vlanif,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct Laggs<'input>(Vec<Lagg<'input>>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Laggs<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let lagg_tag_name: roxmltree::ExpandedName<'_, '_> = "lagg".into();
+
+		let inner: Result<_, crate::Error> =
+			node.children()
+			.filter(|child| child.tag_name() == lagg_tag_name)
+			.map(std::convert::TryInto::try_into)
+			.collect();
+		let inner = inner?;
+
+		Ok(Laggs(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Lagg<'input> {
+	members: &'input str,
+	laggif: &'input str,
+	proto: &'input str,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Lagg<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let members_tag_name: roxmltree::ExpandedName<'_, '_> = "members".into();
+		let laggif_tag_name: roxmltree::ExpandedName<'_, '_> = "laggif".into();
+		let proto_tag_name: roxmltree::ExpandedName<'_, '_> = "proto".into();
+
+		let members = node.children().find(|node| node.tag_name() == members_tag_name).ok_or("laggs.lagg.members not found in config.xml")?;
+		let members = members.text().unwrap_or_default();
+
+		let laggif = node.children().find(|node| node.tag_name() == laggif_tag_name).ok_or("laggs.lagg.laggif not found in config.xml")?;
+		let laggif = laggif.text().ok_or("laggs.lagg.laggif is not a text node")?;
+
+		let proto = node.children().find(|node| node.tag_name() == proto_tag_name).ok_or("laggs.lagg.proto not found in config.xml")?;
+		let proto = proto.text().ok_or("laggs.lagg.proto is not a text node")?;
+
+		Ok(Lagg {
+			members,
+			laggif,
+			proto,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct Ppps<'input>(Vec<Ppp<'input>>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Ppps<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let ppp_tag_name: roxmltree::ExpandedName<'_, '_> = "ppp".into();
+
+		let inner: Result<_, crate::Error> =
+			node.children()
+			.filter(|child| child.tag_name() == ppp_tag_name)
+			.map(std::convert::TryInto::try_into)
+			.collect();
+		let inner = inner?;
+
+		Ok(Ppps(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Ppp<'input> {
+	r#if: &'input str,
+	r#type: &'input str,
+	ports: &'input str,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Ppp<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let if_tag_name: roxmltree::ExpandedName<'_, '_> = "if".into();
+		let type_tag_name: roxmltree::ExpandedName<'_, '_> = "type".into();
+		let ports_tag_name: roxmltree::ExpandedName<'_, '_> = "ports".into();
+
+		let r#if = node.children().find(|node| node.tag_name() == if_tag_name).ok_or("ppps.ppp.if not found in config.xml")?;
+		let r#if = r#if.text().ok_or("ppps.ppp.if is not a text node")?;
+
+		let r#type = node.children().find(|node| node.tag_name() == type_tag_name).ok_or("ppps.ppp.type not found in config.xml")?;
+		let r#type = r#type.text().ok_or("ppps.ppp.type is not a text node")?;
+
+		let ports = node.children().find(|node| node.tag_name() == ports_tag_name).ok_or("ppps.ppp.ports not found in config.xml")?;
+		let ports = ports.text().ok_or("ppps.ppp.ports is not a text node")?;
+
+		Ok(Ppp {
+			r#if,
+			r#type,
+			ports,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct Gateways<'input> {
+	items: std::collections::BTreeMap<&'input str, GatewayItem<'input>>,
+	groups: Vec<GatewayGroupItem<'input>>,
+	defaultgw4: Option<&'input str>,
+	defaultgw6: Option<&'input str>,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Gateways<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let gateway_item_tag_name: roxmltree::ExpandedName<'_, '_> = "gateway_item".into();
+		let gateway_group_tag_name: roxmltree::ExpandedName<'_, '_> = "gateway_group".into();
+		let defaultgw4_tag_name: roxmltree::ExpandedName<'_, '_> = "defaultgw4".into();
+		let defaultgw6_tag_name: roxmltree::ExpandedName<'_, '_> = "defaultgw6".into();
+
+		let mut items: std::collections::BTreeMap<_, _> = Default::default();
+		let mut groups = vec![];
+		let mut defaultgw4 = None;
+		let mut defaultgw6 = None;
+
+		for child in node.children() {
+			let child_tag_name = child.tag_name();
+			if child_tag_name == gateway_item_tag_name {
+				let gateway_item: GatewayItem<'_> = std::convert::TryInto::try_into(child)?;
+				let _ = items.insert(gateway_item.name, gateway_item);
+			}
+			else if child_tag_name == gateway_group_tag_name {
+				groups.push(std::convert::TryInto::try_into(child)?);
+			}
+			else if child_tag_name == defaultgw4_tag_name {
+				// Empty or `-` means the default gateway is chosen automatically, or that there isn't one.
+				defaultgw4 = child.text().filter(|&defaultgw4| !defaultgw4.is_empty() && defaultgw4 != "-");
+			}
+			else if child_tag_name == defaultgw6_tag_name {
+				defaultgw6 = child.text().filter(|&defaultgw6| !defaultgw6.is_empty() && defaultgw6 != "-");
+			}
+		}
+
+		Ok(Gateways {
+			items,
+			groups,
+			defaultgw4,
+			defaultgw6,
+		})
 	}
 }
 
@@ -262,6 +877,15 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Gateways
 struct GatewayItem<'input> {
 	name: &'input str,
 	interface: &'input str,
+	gateway: Option<&'input str>,
+	ipprotocol: Option<&'input str>,
+	monitor: Option<&'input str>,
+	monitor_disable: bool,
+	disabled: bool,
+	latencylow: Option<u64>,
+	latencyhigh: Option<u64>,
+	losslow: Option<u64>,
+	losshigh: Option<u64>,
 }
 
 impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for GatewayItem<'input> {
@@ -270,6 +894,11 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for GatewayI
 	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
 		let interface_tag_name: roxmltree::ExpandedName<'_, '_> = "interface".into();
 		let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+		let gateway_tag_name: roxmltree::ExpandedName<'_, '_> = "gateway".into();
+		let ipprotocol_tag_name: roxmltree::ExpandedName<'_, '_> = "ipprotocol".into();
+		let monitor_tag_name: roxmltree::ExpandedName<'_, '_> = "monitor".into();
+		let monitor_disable_tag_name: roxmltree::ExpandedName<'_, '_> = "monitor_disable".into();
+		let disabled_tag_name: roxmltree::ExpandedName<'_, '_> = "disabled".into();
 
 		let interface = node.children().find(|node| node.tag_name() == interface_tag_name).ok_or("gateways.gateway_item.interface not found in config.xml")?;
 		let interface = interface.text().ok_or("gateways.gateway_item.interface is not a text node")?;
@@ -277,13 +906,597 @@ impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for GatewayI
 		let name = node.children().find(|node| node.tag_name() == name_tag_name).ok_or("gateways.gateway_item.name not found in config.xml")?;
 		let name = name.text().ok_or("gateways.gateway_item.name is not a text node")?;
 
+		let gateway = node.children().find(|node| node.tag_name() == gateway_tag_name).and_then(|gateway| gateway.text());
+
+		let ipprotocol = node.children().find(|node| node.tag_name() == ipprotocol_tag_name).and_then(|ipprotocol| ipprotocol.text());
+
+		let monitor = node.children().find(|node| node.tag_name() == monitor_tag_name).and_then(|monitor| monitor.text());
+
+		let monitor_disable = node.children().any(|node| node.tag_name() == monitor_disable_tag_name);
+
+		let disabled = node.children().any(|node| node.tag_name() == disabled_tag_name);
+
+		// The thresholds are empty if they're left at their defaults.
+		let threshold = |tag_name: &str| -> Result<Option<u64>, crate::Error> {
+			let expanded_tag_name: roxmltree::ExpandedName<'_, '_> = tag_name.into();
+			let threshold = node.children().find(|node| node.tag_name() == expanded_tag_name).and_then(|threshold| threshold.text());
+			let threshold =
+				threshold.filter(|threshold| !threshold.is_empty())
+				.map(|threshold| threshold.parse().map_err(|err| format!("gateways.gateway_item.{} is malformed: {}", tag_name, err)))
+				.transpose()?;
+			Ok(threshold)
+		};
+		let latencylow = threshold("latencylow")?;
+		let latencyhigh = threshold("latencyhigh")?;
+		let losslow = threshold("losslow")?;
+		let losshigh = threshold("losshigh")?;
+
 		Ok(GatewayItem {
+			name,
 			interface,
+			gateway,
+			ipprotocol,
+			monitor,
+			monitor_disable,
+			disabled,
+			latencylow,
+			latencyhigh,
+			losslow,
+			losshigh,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct GatewayGroupItem<'input> {
+	name: &'input str,
+	members: Vec<(&'input str, u32)>,
+	trigger: &'input str,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for GatewayGroupItem<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+		let item_tag_name: roxmltree::ExpandedName<'_, '_> = "item".into();
+		let trigger_tag_name: roxmltree::ExpandedName<'_, '_> = "trigger".into();
+
+		let name = node.children().find(|node| node.tag_name() == name_tag_name).ok_or("gateways.gateway_group.name not found in config.xml")?;
+		let name = name.text().ok_or("gateways.gateway_group.name is not a text node")?;
+
+		// Each item is of the form `GATEWAY_NAME|TIER|VIP`
+		let members: Result<_, crate::Error> =
+			node.children()
+			.filter(|node| node.tag_name() == item_tag_name)
+			.map(|item| {
+				let item = item.text().ok_or("gateways.gateway_group.item is not a text node")?;
+				let mut item_parts = item.split('|');
+				let gateway_name = item_parts.next().ok_or("gateways.gateway_group.item is malformed")?;
+				let tier = item_parts.next().ok_or("gateways.gateway_group.item is malformed")?;
+				let tier = tier.parse().map_err(|err| format!("gateways.gateway_group.item tier is malformed: {}", err))?;
+				Ok((gateway_name, tier))
+			})
+			.collect();
+		let members = members?;
+
+		let trigger = node.children().find(|node| node.tag_name() == trigger_tag_name).and_then(|trigger| trigger.text()).unwrap_or("down");
+
+		Ok(GatewayGroupItem {
+			name,
+			members,
+			trigger,
+		})
+	}
+}
+
+/// Static mappings of the DHCP servers that are enabled, and the names of the interfaces they're on
+#[derive(Debug)]
+struct Dhcpd<'input>(Vec<(&'input str, DhcpStaticMap<'input>)>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Dhcpd<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let enable_tag_name: roxmltree::ExpandedName<'_, '_> = "enable".into();
+		let staticmap_tag_name: roxmltree::ExpandedName<'_, '_> = "staticmap".into();
+
+		let mut inner = vec![];
+
+		for child in node.children().filter(roxmltree::Node::is_element) {
+			if !child.children().any(|node| node.tag_name() == enable_tag_name) {
+				continue;
+			}
+
+			let interface_name = child.tag_name().name();
+
+			for staticmap in child.children().filter(|node| node.tag_name() == staticmap_tag_name) {
+				inner.push((interface_name, std::convert::TryInto::try_into(staticmap)?));
+			}
+		}
+
+		Ok(Dhcpd(inner))
+	}
+}
+
+#[derive(Debug)]
+struct DhcpStaticMap<'input> {
+	// `None` for mappings that match the client identifier instead
+	mac: Option<&'input str>,
+	ipaddr: Option<&'input str>,
+	hostname: Option<&'input str>,
+	descr: Option<&'input str>,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for DhcpStaticMap<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let mac_tag_name: roxmltree::ExpandedName<'_, '_> = "mac".into();
+		let ipaddr_tag_name: roxmltree::ExpandedName<'_, '_> = "ipaddr".into();
+		let hostname_tag_name: roxmltree::ExpandedName<'_, '_> = "hostname".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+
+		let mac = node.children().find(|node| node.tag_name() == mac_tag_name).and_then(|mac| mac.text());
+		let ipaddr = node.children().find(|node| node.tag_name() == ipaddr_tag_name).and_then(|ipaddr| ipaddr.text());
+		let hostname = node.children().find(|node| node.tag_name() == hostname_tag_name).and_then(|hostname| hostname.text());
+		let descr = node.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text());
+
+		Ok(DhcpStaticMap {
+			mac,
+			ipaddr,
+			hostname,
+			descr,
+		})
+	}
+}
+
+/// CARP VIPs. Other kinds of VIPs, like IP aliases and proxy ARP, aren't included.
+#[derive(Debug)]
+struct VirtualIps<'input>(Vec<Vip<'input>>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for VirtualIps<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let vip_tag_name: roxmltree::ExpandedName<'_, '_> = "vip".into();
+		let mode_tag_name: roxmltree::ExpandedName<'_, '_> = "mode".into();
+
+		let mut inner = vec![];
+
+		for child in node.children().filter(|child| child.tag_name() == vip_tag_name) {
+			let mode = child.children().find(|node| node.tag_name() == mode_tag_name).and_then(|mode| mode.text());
+			if mode != Some("carp") {
+				continue;
+			}
+
+			inner.push(std::convert::TryInto::try_into(child)?);
+		}
+
+		Ok(VirtualIps(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Vip<'input> {
+	interface: &'input str,
+	vhid: u8,
+	subnet: &'input str,
+	descr: Option<&'input str>,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Vip<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let interface_tag_name: roxmltree::ExpandedName<'_, '_> = "interface".into();
+		let vhid_tag_name: roxmltree::ExpandedName<'_, '_> = "vhid".into();
+		let subnet_tag_name: roxmltree::ExpandedName<'_, '_> = "subnet".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+
+		let interface = node.children().find(|node| node.tag_name() == interface_tag_name).ok_or("virtualip.vip.interface not found in config.xml")?;
+		let interface = interface.text().ok_or("virtualip.vip.interface is not a text node")?;
+
+		let vhid = node.children().find(|node| node.tag_name() == vhid_tag_name).ok_or("virtualip.vip.vhid not found in config.xml")?;
+		let vhid = vhid.text().ok_or("virtualip.vip.vhid is not a text node")?;
+		let vhid = vhid.parse().map_err(|err| format!("virtualip.vip.vhid is not a valid VHID: {}", err))?;
+
+		let subnet = node.children().find(|node| node.tag_name() == subnet_tag_name).ok_or("virtualip.vip.subnet not found in config.xml")?;
+		let subnet = subnet.text().ok_or("virtualip.vip.subnet is not a text node")?;
+
+		let descr = node.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text());
+
+		Ok(Vip {
+			interface,
+			vhid,
+			subnet,
+			descr,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct HaSync {
+	pfsyncenabled: bool,
+}
+
+impl std::convert::TryFrom<roxmltree::Node<'_, '_>> for HaSync {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'_, '_>) -> Result<Self, Self::Error> {
+		let pfsyncenabled_tag_name: roxmltree::ExpandedName<'_, '_> = "pfsyncenabled".into();
+
+		let pfsyncenabled =
+			node.children().find(|node| node.tag_name() == pfsyncenabled_tag_name).and_then(|pfsyncenabled| pfsyncenabled.text())
+			== Some("on");
+
+		Ok(HaSync {
+			pfsyncenabled,
+		})
+	}
+}
+
+/// Enabled ALTQ queues, as their interface names, queue names (`None` for root queues) and descriptions
+#[derive(Debug)]
+struct Shaper<'input>(Vec<(&'input str, Option<&'input str>, Option<&'input str>)>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Shaper<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		fn add_queues<'input>(
+			node: roxmltree::Node<'input, 'input>,
+			is_root: bool,
+			result: &mut Vec<(&'input str, Option<&'input str>, Option<&'input str>)>,
+		) -> Result<(), crate::Error> {
+			let queue_tag_name: roxmltree::ExpandedName<'_, '_> = "queue".into();
+			let interface_tag_name: roxmltree::ExpandedName<'_, '_> = "interface".into();
+			let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+			let description_tag_name: roxmltree::ExpandedName<'_, '_> = "description".into();
+			let enabled_tag_name: roxmltree::ExpandedName<'_, '_> = "enabled".into();
+
+			for queue in node.children().filter(|node| node.tag_name() == queue_tag_name) {
+				let enabled = queue.children().find(|node| node.tag_name() == enabled_tag_name).and_then(|enabled| enabled.text());
+				if enabled != Some("on") {
+					continue;
+				}
+
+				let interface = queue.children().find(|node| node.tag_name() == interface_tag_name).ok_or("shaper.queue.interface not found in config.xml")?;
+				let interface = interface.text().ok_or("shaper.queue.interface is not a text node")?;
+
+				let name =
+					if is_root {
+						None
+					}
+					else {
+						let name = queue.children().find(|node| node.tag_name() == name_tag_name).ok_or("shaper.queue.name not found in config.xml")?;
+						Some(name.text().ok_or("shaper.queue.name is not a text node")?)
+					};
+
+				let description = queue.children().find(|node| node.tag_name() == description_tag_name).and_then(|description| description.text());
+
+				result.push((interface, name, description));
+
+				add_queues(queue, false, result)?;
+			}
+
+			Ok(())
+		}
+
+		let mut inner = vec![];
+		add_queues(node, true, &mut inner)?;
+		Ok(Shaper(inner))
+	}
+}
+
+/// Enabled limiters
+#[derive(Debug)]
+struct DnShaper<'input>(Vec<DnShaperQueue<'input>>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for DnShaper<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let queue_tag_name: roxmltree::ExpandedName<'_, '_> = "queue".into();
+
+		let mut inner = vec![];
+
+		for queue in node.children().filter(|node| node.tag_name() == queue_tag_name) {
+			if let Some(queue) = DnShaperQueue::parse(queue)? {
+				inner.push(queue);
+			}
+		}
+
+		Ok(DnShaper(inner))
+	}
+}
+
+/// A limiter, or a queue of a limiter
+#[derive(Debug)]
+struct DnShaperQueue<'input> {
+	name: &'input str,
+	number: u32,
+	queues: Vec<DnShaperQueue<'input>>,
+}
+
+impl<'input> DnShaperQueue<'input> {
+	/// Returns `None` if the limiter or queue is disabled.
+	fn parse(node: roxmltree::Node<'input, 'input>) -> Result<Option<Self>, crate::Error> {
+		let queue_tag_name: roxmltree::ExpandedName<'_, '_> = "queue".into();
+		let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+		let number_tag_name: roxmltree::ExpandedName<'_, '_> = "number".into();
+		let enabled_tag_name: roxmltree::ExpandedName<'_, '_> = "enabled".into();
+
+		let enabled = node.children().find(|node| node.tag_name() == enabled_tag_name).and_then(|enabled| enabled.text());
+		if enabled != Some("on") {
+			return Ok(None);
+		}
+
+		let name = node.children().find(|node| node.tag_name() == name_tag_name).ok_or("dnshaper.queue.name not found in config.xml")?;
+		let name = name.text().ok_or("dnshaper.queue.name is not a text node")?;
+
+		let number = node.children().find(|node| node.tag_name() == number_tag_name).ok_or("dnshaper.queue.number not found in config.xml")?;
+		let number = number.text().ok_or("dnshaper.queue.number is not a text node")?;
+		let number = number.parse().map_err(|err| format!("dnshaper.queue.number is malformed: {}", err))?;
+
+		let mut queues = vec![];
+		for queue in node.children().filter(|node| node.tag_name() == queue_tag_name) {
+			if let Some(queue) = DnShaperQueue::parse(queue)? {
+				queues.push(queue);
+			}
+		}
+
+		Ok(Some(DnShaperQueue {
 			name,
+			number,
+			queues,
+		}))
+	}
+}
+
+/// Tracker IDs and descriptions of the filter rules that have both
+#[derive(Debug)]
+struct Filter<'input>(Vec<(u32, &'input str)>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Filter<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let rule_tag_name: roxmltree::ExpandedName<'_, '_> = "rule".into();
+		let tracker_tag_name: roxmltree::ExpandedName<'_, '_> = "tracker".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+
+		let mut inner = vec![];
+
+		for rule in node.children().filter(|node| node.tag_name() == rule_tag_name) {
+			// Rules created by old versions of pfSense don't have a tracker until they're edited.
+			let Some(tracker) = rule.children().find(|node| node.tag_name() == tracker_tag_name) else {
+				continue;
+			};
+			let tracker = tracker.text().ok_or("filter.rule.tracker is not a text node")?;
+			let tracker = tracker.parse().map_err(|err| format!("filter.rule.tracker is malformed: {}", err))?;
+
+			let Some(descr) = rule.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text()) else {
+				continue;
+			};
+
+			inner.push((tracker, descr));
+		}
+
+		Ok(Filter(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Aliases<'input>(Vec<Alias<'input>>);
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Aliases<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let alias_tag_name: roxmltree::ExpandedName<'_, '_> = "alias".into();
+
+		let inner: Result<_, crate::Error> =
+			node.children()
+			.filter(|child| child.tag_name() == alias_tag_name)
+			.map(std::convert::TryInto::try_into)
+			.collect();
+		let inner = inner?;
+
+		Ok(Aliases(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Alias<'input> {
+	name: &'input str,
+
+	/// Like `host`, `network`, `port`, `url`, `url_ports`, `urltable` or `urltable_ports`
+	r#type: &'input str,
+
+	descr: Option<&'input str>,
+}
+
+impl<'input> std::convert::TryFrom<roxmltree::Node<'input, 'input>> for Alias<'input> {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'input, 'input>) -> Result<Self, Self::Error> {
+		let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+		let type_tag_name: roxmltree::ExpandedName<'_, '_> = "type".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+
+		let name = node.children().find(|node| node.tag_name() == name_tag_name).ok_or("aliases.alias.name not found in config.xml")?;
+		let name = name.text().ok_or("aliases.alias.name is not a text node")?;
+
+		let r#type = node.children().find(|node| node.tag_name() == type_tag_name).ok_or("aliases.alias.type not found in config.xml")?;
+		let r#type = r#type.text().ok_or("aliases.alias.type is not a text node")?;
+
+		let descr = node.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text());
+
+		Ok(Alias {
+			name,
+			r#type,
+			descr,
 		})
 	}
 }
 
+#[derive(Debug)]
+struct Unbound {
+	enable: bool,
+}
+
+impl std::convert::TryFrom<roxmltree::Node<'_, '_>> for Unbound {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'_, '_>) -> Result<Self, Self::Error> {
+		let enable_tag_name: roxmltree::ExpandedName<'_, '_> = "enable".into();
+
+		// The DNS resolver is enabled by the presence of an empty `<enable/>` element.
+		let enable = node.children().any(|node| node.tag_name() == enable_tag_name);
+
+		Ok(Unbound {
+			enable,
+		})
+	}
+}
+
+#[derive(Debug)]
+struct OpenVpn(Vec<VpnTunnel>);
+
+impl std::convert::TryFrom<roxmltree::Node<'_, '_>> for OpenVpn {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'_, '_>) -> Result<Self, Self::Error> {
+		let server_tag_name: roxmltree::ExpandedName<'_, '_> = "openvpn-server".into();
+		let client_tag_name: roxmltree::ExpandedName<'_, '_> = "openvpn-client".into();
+		let vpnid_tag_name: roxmltree::ExpandedName<'_, '_> = "vpnid".into();
+		let description_tag_name: roxmltree::ExpandedName<'_, '_> = "description".into();
+		let disable_tag_name: roxmltree::ExpandedName<'_, '_> = "disable".into();
+
+		let mut inner = vec![];
+
+		for child in node.children() {
+			let (kind, prefix) =
+				if child.tag_name() == server_tag_name {
+					(VpnKind::OpenVpnServer, "server")
+				}
+				else if child.tag_name() == client_tag_name {
+					(VpnKind::OpenVpnClient, "client")
+				}
+				else {
+					continue;
+				};
+
+			if child.children().any(|node| node.tag_name() == disable_tag_name) {
+				continue;
+			}
+
+			let vpnid = child.children().find(|node| node.tag_name() == vpnid_tag_name).ok_or("openvpn.*.vpnid not found in config.xml")?;
+			let vpnid = vpnid.text().ok_or("openvpn.*.vpnid is not a text node")?;
+
+			let id = format!("{}{}", prefix, vpnid);
+
+			let description =
+				child.children().find(|node| node.tag_name() == description_tag_name).and_then(|description| description.text())
+				.map_or_else(|| id.clone(), ToOwned::to_owned);
+
+			inner.push(VpnTunnel {
+				kind,
+				id,
+				description,
+			});
+		}
+
+		Ok(OpenVpn(inner))
+	}
+}
+
+#[derive(Debug)]
+struct Ipsec(Vec<VpnTunnel>);
+
+impl std::convert::TryFrom<roxmltree::Node<'_, '_>> for Ipsec {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'_, '_>) -> Result<Self, Self::Error> {
+		let phase1_tag_name: roxmltree::ExpandedName<'_, '_> = "phase1".into();
+		let ikeid_tag_name: roxmltree::ExpandedName<'_, '_> = "ikeid".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+		let disabled_tag_name: roxmltree::ExpandedName<'_, '_> = "disabled".into();
+
+		let mut inner = vec![];
+
+		for child in node.children().filter(|child| child.tag_name() == phase1_tag_name) {
+			if child.children().any(|node| node.tag_name() == disabled_tag_name) {
+				continue;
+			}
+
+			let ikeid = child.children().find(|node| node.tag_name() == ikeid_tag_name).ok_or("ipsec.phase1.ikeid not found in config.xml")?;
+			let ikeid = ikeid.text().ok_or("ipsec.phase1.ikeid is not a text node")?;
+
+			// pfSense names the swanctl connection of each phase 1 after its ikeid
+			let id = format!("con{}", ikeid);
+
+			let description =
+				child.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text())
+				.map_or_else(|| id.clone(), ToOwned::to_owned);
+
+			inner.push(VpnTunnel {
+				kind: VpnKind::Ipsec,
+				id,
+				description,
+			});
+		}
+
+		Ok(Ipsec(inner))
+	}
+}
+
+#[derive(Debug)]
+struct WireGuard(Vec<VpnTunnel>);
+
+impl std::convert::TryFrom<roxmltree::Node<'_, '_>> for WireGuard {
+	type Error = crate::Error;
+
+	fn try_from(node: roxmltree::Node<'_, '_>) -> Result<Self, Self::Error> {
+		let tunnels_tag_name: roxmltree::ExpandedName<'_, '_> = "tunnels".into();
+		let item_tag_name: roxmltree::ExpandedName<'_, '_> = "item".into();
+		let name_tag_name: roxmltree::ExpandedName<'_, '_> = "name".into();
+		let descr_tag_name: roxmltree::ExpandedName<'_, '_> = "descr".into();
+		let enabled_tag_name: roxmltree::ExpandedName<'_, '_> = "enabled".into();
+
+		let mut inner = vec![];
+
+		let items =
+			node.children()
+			.filter(|child| child.tag_name() == tunnels_tag_name)
+			.flat_map(|tunnels| tunnels.children())
+			.filter(|child| child.tag_name() == item_tag_name);
+
+		for item in items {
+			let enabled = item.children().find(|node| node.tag_name() == enabled_tag_name).and_then(|enabled| enabled.text());
+			if enabled != Some("yes") {
+				continue;
+			}
+
+			let name = item.children().find(|node| node.tag_name() == name_tag_name).ok_or("wireguard.tunnels.item.name not found in config.xml")?;
+			let name = name.text().ok_or("wireguard.tunnels.item.name is not a text node")?;
+
+			let description =
+				item.children().find(|node| node.tag_name() == descr_tag_name).and_then(|descr| descr.text())
+				.unwrap_or(name);
+
+			inner.push(VpnTunnel {
+				kind: VpnKind::WireGuard,
+				id: name.to_owned(),
+				description: description.to_owned(),
+			});
+		}
+
+		Ok(WireGuard(inner))
+	}
+}
+
 #[derive(Debug)]
 struct InstalledPackages<'input>(Vec<(&'input str, &'input str)>);
 
@@ -0,0 +1,363 @@
+/// Resolves IP addresses to hostnames in the background, using PTR queries sent to the configured DNS server.
+#[derive(Debug)]
+pub(crate) struct Resolver {
+	cache: std::sync::Arc<std::sync::Mutex<Cache>>,
+	requests: std::sync::mpsc::SyncSender<std::net::IpAddr>,
+}
+
+const NUM_WORKERS: usize = 4;
+
+// How many lookups can be waiting for a worker. Addresses that arrive when the queue is full are looked up the next time they're seen.
+const MAX_PENDING_REQUESTS: usize = 256;
+
+// How long to remember a name, or the lack of one.
+const POSITIVE_CACHE_DURATION: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+const NEGATIVE_CACHE_DURATION: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+impl Resolver {
+	pub(crate) fn new(config: &crate::config::ReverseDns) -> Self {
+		let cache = std::sync::Arc::new(std::sync::Mutex::new(Cache {
+			entries: Default::default(),
+			capacity: config.cache_size,
+			clock: 0,
+		}));
+
+		let (requests, requests_receiver) = std::sync::mpsc::sync_channel(MAX_PENDING_REQUESTS);
+		let requests_receiver = std::sync::Arc::new(std::sync::Mutex::new(requests_receiver));
+
+		let timeout = std::time::Duration::from_millis(config.timeout_ms);
+
+		for _ in 0..NUM_WORKERS {
+			let cache = cache.clone();
+			let requests_receiver = requests_receiver.clone();
+			let server = config.server.clone();
+
+			let _ = std::thread::spawn(move || loop {
+				let ip = {
+					let requests_receiver = requests_receiver.lock().expect("could not lock reverse DNS request queue");
+					match requests_receiver.recv() {
+						Ok(ip) => ip,
+						Err(std::sync::mpsc::RecvError) => break,
+					}
+				};
+
+				// Failures are cached the same as a missing PTR record, so that an unreachable server isn't hammered with queries.
+				let name = query_ptr(&server, ip, timeout).ok().flatten();
+
+				let mut cache = cache.lock().expect("could not lock reverse DNS cache");
+				cache.insert(ip, name);
+			});
+		}
+
+		Resolver {
+			cache,
+			requests,
+		}
+	}
+
+	/// Returns the cached name of the given address, if any.
+	///
+	/// If the address isn't in the cache, a lookup is started in the background and `None` is returned.
+	pub(crate) fn lookup(&self, ip: std::net::IpAddr) -> Option<String> {
+		let mut cache = self.cache.lock().expect("could not lock reverse DNS cache");
+		match cache.get(ip) {
+			Some(CacheEntryState::Resolved(name)) => name,
+			Some(CacheEntryState::Pending) => None,
+			None => {
+				if self.requests.try_send(ip).is_ok() {
+					cache.insert_pending(ip);
+				}
+				None
+			},
+		}
+	}
+}
+
+/// An LRU cache of names.
+#[derive(Debug)]
+struct Cache {
+	entries: std::collections::HashMap<std::net::IpAddr, CacheEntry>,
+	capacity: usize,
+
+	// Incremented on every access, to order entries by when they were last used.
+	clock: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+	state: CacheEntryState,
+	expires: std::time::Instant,
+	last_used: u64,
+}
+
+#[derive(Clone, Debug)]
+enum CacheEntryState {
+	Pending,
+	Resolved(Option<String>),
+}
+
+impl Cache {
+	fn get(&mut self, ip: std::net::IpAddr) -> Option<CacheEntryState> {
+		self.clock += 1;
+
+		let entry = self.entries.get_mut(&ip)?;
+		if entry.expires < std::time::Instant::now() {
+			let _ = self.entries.remove(&ip);
+			return None;
+		}
+
+		entry.last_used = self.clock;
+		Some(entry.state.clone())
+	}
+
+	fn insert_pending(&mut self, ip: std::net::IpAddr) {
+		// Pending entries don't expire on their own. They're replaced when the lookup finishes.
+		self.insert_entry(ip, CacheEntryState::Pending, std::time::Instant::now() + POSITIVE_CACHE_DURATION);
+	}
+
+	fn insert(&mut self, ip: std::net::IpAddr, name: Option<String>) {
+		let cache_duration = if name.is_some() { POSITIVE_CACHE_DURATION } else { NEGATIVE_CACHE_DURATION };
+		self.insert_entry(ip, CacheEntryState::Resolved(name), std::time::Instant::now() + cache_duration);
+	}
+
+	fn insert_entry(&mut self, ip: std::net::IpAddr, state: CacheEntryState, expires: std::time::Instant) {
+		self.clock += 1;
+
+		let _ = self.entries.insert(ip, CacheEntry {
+			state,
+			expires,
+			last_used: self.clock,
+		});
+
+		while self.entries.len() > self.capacity {
+			let least_recently_used =
+				self.entries.iter()
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(&ip, _)| ip)
+				.expect("cache is not empty");
+			let _ = self.entries.remove(&least_recently_used);
+		}
+	}
+}
+
+const DNS_TYPE_PTR: u16 = 12;
+const DNS_CLASS_IN: u16 = 1;
+
+fn query_ptr(server: &str, ip: std::net::IpAddr, timeout: std::time::Duration) -> Result<Option<String>, crate::Error> {
+	let server = std::net::ToSocketAddrs::to_socket_addrs(server)?.next().ok_or("reverse DNS server address did not resolve")?;
+
+	let bind_address: std::net::SocketAddr = match server {
+		std::net::SocketAddr::V4(_) => (std::net::Ipv4Addr::UNSPECIFIED, 0).into(),
+		std::net::SocketAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into(),
+	};
+	let socket = std::net::UdpSocket::bind(bind_address)?;
+	socket.connect(server)?;
+	socket.set_read_timeout(Some(timeout))?;
+
+	// A random ID makes it harder for anyone else on the network to spoof a response.
+	let mut id = [0_u8; 2];
+	std::io::Read::read_exact(&mut std::fs::File::open("/dev/urandom")?, &mut id)?;
+	let id = u16::from_ne_bytes(id);
+
+	let mut query = vec![];
+	query.extend_from_slice(&id.to_be_bytes());
+	query.extend_from_slice(&0x0100_u16.to_be_bytes()); // Recursion desired
+	query.extend_from_slice(&1_u16.to_be_bytes()); // QDCOUNT
+	query.extend_from_slice(&[0; 6]); // ANCOUNT, NSCOUNT, ARCOUNT
+	for label in ptr_name(ip).split('.') {
+		#[allow(clippy::cast_possible_truncation)] // Labels of a PTR name are at most 7 bytes.
+		query.push(label.len() as u8);
+		query.extend_from_slice(label.as_bytes());
+	}
+	query.push(0);
+	query.extend_from_slice(&DNS_TYPE_PTR.to_be_bytes());
+	query.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+
+	let _ = socket.send(&query)?;
+
+	let deadline = std::time::Instant::now() + timeout;
+	let mut response = [0_u8; 512];
+	loop {
+		let len = socket.recv(&mut response)?;
+		let response = &response[..len];
+		if response.get(..2) == Some(&id.to_be_bytes()[..]) {
+			return parse_ptr_response(response);
+		}
+
+		// Stale response to some other query. Keep waiting for ours, but don't let it extend the timeout.
+		let remaining = deadline.checked_duration_since(std::time::Instant::now()).ok_or("reverse DNS query timed out")?;
+		socket.set_read_timeout(Some(remaining))?;
+	}
+}
+
+fn ptr_name(ip: std::net::IpAddr) -> String {
+	match ip {
+		std::net::IpAddr::V4(ip) => {
+			let [a, b, c, d] = ip.octets();
+			format!("{}.{}.{}.{}.in-addr.arpa", d, c, b, a)
+		},
+
+		std::net::IpAddr::V6(ip) => {
+			let mut result = String::new();
+			for octet in ip.octets().iter().rev() {
+				result.push(std::char::from_digit(u32::from(octet & 0x0F), 16).expect("nibble is a valid hex digit"));
+				result.push('.');
+				result.push(std::char::from_digit(u32::from(octet >> 4), 16).expect("nibble is a valid hex digit"));
+				result.push('.');
+			}
+			result.push_str("ip6.arpa");
+			result
+		},
+	}
+}
+
+fn parse_ptr_response(response: &[u8]) -> Result<Option<String>, crate::Error> {
+	let read_u16 = |offset: usize| -> Result<u16, crate::Error> {
+		let bytes = response.get(offset..(offset + 2)).ok_or("DNS response is truncated")?;
+		Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+	};
+
+	let flags = read_u16(2)?;
+	let rcode = flags & 0x000F;
+	if rcode != 0 {
+		// NXDOMAIN, SERVFAIL, etc.
+		return Ok(None);
+	}
+
+	let qdcount = read_u16(4)?;
+	let ancount = read_u16(6)?;
+
+	let mut offset = 12;
+
+	for _ in 0..qdcount {
+		let (_, name_len) = read_name(response, offset)?;
+		offset += name_len + 4;
+	}
+
+	for _ in 0..ancount {
+		let (_, name_len) = read_name(response, offset)?;
+		offset += name_len;
+
+		let r#type = read_u16(offset)?;
+		let class = read_u16(offset + 2)?;
+		let rdlength = usize::from(read_u16(offset + 8)?);
+		offset += 10;
+
+		if r#type == DNS_TYPE_PTR && class == DNS_CLASS_IN {
+			let (name, _) = read_name(response, offset)?;
+
+			// The name is printed to the terminal, and whoever controls the PTR zone of the address controls the name,
+			// so anything that isn't a plain hostname is treated as no name rather than risk it containing escape sequences.
+			if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_')) {
+				return Ok(None);
+			}
+
+			return Ok(Some(name));
+		}
+
+		offset += rdlength;
+	}
+
+	Ok(None)
+}
+
+/// Reads a possibly-compressed name at the given offset. Returns the name and the number of bytes it occupies at that offset.
+fn read_name(message: &[u8], offset: usize) -> Result<(String, usize), crate::Error> {
+	let mut labels = vec![];
+	let mut position = offset;
+	let mut len_at_offset = None;
+
+	// Bounds the number of compression pointers followed, in case of a pointer loop.
+	for _ in 0..128 {
+		let len = *message.get(position).ok_or("DNS response is truncated")?;
+		match len {
+			0 => {
+				let len_at_offset = len_at_offset.unwrap_or_else(|| position + 1 - offset);
+				return Ok((labels.join("."), len_at_offset));
+			},
+
+			len if len & 0xC0 == 0xC0 => {
+				let low = *message.get(position + 1).ok_or("DNS response is truncated")?;
+				if len_at_offset.is_none() {
+					len_at_offset = Some(position + 2 - offset);
+				}
+				position = (usize::from(len & 0x3F) << 8) | usize::from(low);
+			},
+
+			len => {
+				let len = usize::from(len);
+				let label = message.get((position + 1)..(position + 1 + len)).ok_or("DNS response is truncated")?;
+				labels.push(String::from_utf8_lossy(label).into_owned());
+				position += 1 + len;
+			},
+		}
+	}
+
+	Err("DNS response contains a compression loop".into())
+}
+
+#[cfg(test)]
+mod tests {
+	// A response to a PTR query for 1.2.3.4 with the given flags, and an answer with the given RDATA.
+	//
+	// The question name starts at offset 12, and its `arpa` label at offset 28. The answer starts at offset 38, and its RDATA at offset 50.
+	fn response(flags: u16, rdata: &[u8]) -> Vec<u8> {
+		let mut result = vec![0x12, 0x34];
+		result.extend_from_slice(&flags.to_be_bytes());
+		result.extend_from_slice(&[0, 1, 0, 1, 0, 0, 0, 0]); // QDCOUNT, ANCOUNT, NSCOUNT, ARCOUNT
+		result.extend_from_slice(b"\x014\x013\x012\x011\x07in-addr\x04arpa\x00");
+		result.extend_from_slice(&[0, 12, 0, 1]); // PTR, IN
+
+		result.extend_from_slice(&[0xC0, 12]); // Pointer to the question name
+		result.extend_from_slice(&[0, 12, 0, 1]); // PTR, IN
+		result.extend_from_slice(&[0, 0, 0x0E, 0x10]); // TTL
+		#[allow(clippy::cast_possible_truncation)] // Test RDATA is short
+		result.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+		result.extend_from_slice(rdata);
+		result
+	}
+
+	#[test]
+	fn ptr() {
+		let response = response(0x8180, b"\x06router\x07example\x03com\x00");
+		assert_eq!(super::parse_ptr_response(&response).unwrap().as_deref(), Some("router.example.com"));
+	}
+
+	#[test]
+	fn compressed_name() {
+		let response = response(0x8180, b"\x06router\xC0\x1C");
+		assert_eq!(super::parse_ptr_response(&response).unwrap().as_deref(), Some("router.arpa"));
+
+		// The length of a compressed name at its offset ends at the pointer.
+		assert_eq!(super::read_name(&response, 38).unwrap(), ("4.3.2.1.in-addr.arpa".to_owned(), 2));
+	}
+
+	#[test]
+	fn pointer_loop() {
+		assert!(super::read_name(&[0xC0, 0x00], 0).is_err());
+
+		let response = response(0x8180, b"\x06router\xC0\x39");
+		assert!(super::parse_ptr_response(&response).is_err());
+	}
+
+	#[test]
+	fn truncated() {
+		let response = response(0x8180, b"\x06router\x07example\x03com\x00");
+		assert!(super::parse_ptr_response(&response[..(response.len() - 5)]).is_err());
+		assert!(super::parse_ptr_response(&response[..3]).is_err());
+	}
+
+	#[test]
+	fn nxdomain() {
+		let mut response = response(0x8183, b"");
+		let _ = response.drain(38..);
+		response[7] = 0; // ANCOUNT
+		assert_eq!(super::parse_ptr_response(&response).unwrap(), None);
+	}
+
+	#[test]
+	fn not_a_hostname() {
+		let response = response(0x8180, b"\x08evil\x1B[2J\x00");
+		assert_eq!(super::parse_ptr_response(&response).unwrap(), None);
+	}
+}
@@ -0,0 +1,151 @@
+#[derive(Debug)]
+pub(crate) struct Detector {
+	default_thresholds: Thresholds,
+	interface_thresholds: std::collections::BTreeMap<String, Thresholds>,
+
+	// Destination ports of recent connection attempts from each source, oldest first.
+	recent: std::collections::HashMap<(String, std::net::IpAddr), std::collections::VecDeque<(std::time::Instant, u16)>>,
+
+	alerts: std::collections::BTreeMap<(String, std::net::IpAddr, AlertKind), Alert>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Thresholds {
+	window: std::time::Duration,
+	port_scan: Option<usize>,
+	brute_force: Option<usize>,
+}
+
+impl Thresholds {
+	const DEFAULT: Self = Thresholds {
+		window: std::time::Duration::from_secs(60),
+		port_scan: Some(10),
+		brute_force: Some(20),
+	};
+
+	/// Returns these thresholds overridden by the ones that are set in `config`.
+	fn merge(self, config: &crate::config::DetectionThresholds) -> Self {
+		Thresholds {
+			window: config.window.map_or(self.window, std::time::Duration::from_secs),
+			port_scan: config.port_scan.map_or(self.port_scan, |port_scan| Some(port_scan).filter(|&port_scan| port_scan > 0)),
+			brute_force: config.brute_force.map_or(self.brute_force, |brute_force| Some(brute_force).filter(|&brute_force| brute_force > 0)),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum AlertKind {
+	/// Many distinct destination ports.
+	PortScan,
+
+	/// Many attempts on the same destination port.
+	BruteForce { port: u16 },
+}
+
+#[derive(Debug)]
+pub(crate) struct Alert {
+	pub(crate) interface: String,
+	pub(crate) source: std::net::IpAddr,
+	pub(crate) kind: AlertKind,
+	pub(crate) count: usize,
+	pub(crate) window: std::time::Duration,
+	last_seen: std::time::Instant,
+}
+
+impl Detector {
+	pub(crate) fn new(config: Option<&crate::config::Detection>) -> Self {
+		let (default_thresholds, interface_thresholds) = match config {
+			Some(config) => {
+				let default_thresholds = Thresholds::DEFAULT.merge(&config.default);
+				let interface_thresholds =
+					config.interfaces.iter()
+					.map(|(interface, thresholds)| (interface.clone(), default_thresholds.merge(thresholds)))
+					.collect();
+				(default_thresholds, interface_thresholds)
+			},
+			None => (Thresholds { window: Default::default(), port_scan: None, brute_force: None }, Default::default()),
+		};
+
+		Detector {
+			default_thresholds,
+			interface_thresholds,
+			recent: Default::default(),
+			alerts: Default::default(),
+		}
+	}
+
+	pub(crate) fn observe(&mut self, log: &crate::firewall_logs::Log) {
+		let (source, destination_port) = match log.protocol {
+			crate::firewall_logs::Protocol::Icmp { .. } => return,
+			crate::firewall_logs::Protocol::Tcp { source, destination } |
+			crate::firewall_logs::Protocol::Udp { source, destination } => (source.ip(), destination.port()),
+		};
+
+		let thresholds = *self.interface_thresholds.get(&log.interface).unwrap_or(&self.default_thresholds);
+		if thresholds.port_scan.is_none() && thresholds.brute_force.is_none() {
+			return;
+		}
+
+		let now = std::time::Instant::now();
+
+		let recent = self.recent.entry((log.interface.clone(), source)).or_default();
+		recent.push_back((now, destination_port));
+		while let Some(&(time, _)) = recent.front() {
+			if now.duration_since(time) <= thresholds.window {
+				break;
+			}
+			let _ = recent.pop_front();
+		}
+
+		let mut port_counts: std::collections::BTreeMap<u16, usize> = Default::default();
+		for &(_, port) in &*recent {
+			*port_counts.entry(port).or_default() += 1;
+		}
+
+		let mut raise = |kind, count| {
+			let alert = self.alerts.entry((log.interface.clone(), source, kind)).or_insert_with(|| Alert {
+				interface: log.interface.clone(),
+				source,
+				kind,
+				count,
+				window: thresholds.window,
+				last_seen: now,
+			});
+			alert.count = count;
+			alert.last_seen = now;
+		};
+
+		if let Some(port_scan) = thresholds.port_scan {
+			if port_counts.len() >= port_scan {
+				raise(AlertKind::PortScan, port_counts.len());
+			}
+		}
+
+		if let Some(brute_force) = thresholds.brute_force {
+			let count = port_counts[&destination_port];
+			if count >= brute_force {
+				raise(AlertKind::BruteForce { port: destination_port }, count);
+			}
+		}
+
+		// Forget sources that have gone quiet, so that a scan from many spoofed sources doesn't grow this without bound.
+		if self.recent.len() > 1024 {
+			let default_window = self.default_thresholds.window;
+			let interface_thresholds = &self.interface_thresholds;
+			self.recent.retain(|(interface, _), recent| {
+				let window = interface_thresholds.get(interface).map_or(default_window, |thresholds| thresholds.window);
+				recent.back().is_some_and(|&(time, _)| now.duration_since(time) <= window)
+			});
+		}
+	}
+
+	/// Alerts that were raised within their window, newest first.
+	pub(crate) fn alerts(&mut self) -> Vec<&Alert> {
+		let now = std::time::Instant::now();
+		self.alerts.retain(|_, alert| now.duration_since(alert.last_seen) <= alert.window);
+
+		let mut result: Vec<_> = self.alerts.values().collect();
+		result.sort_by_key(|alert| std::cmp::Reverse(alert.last_seen));
+		result
+	}
+}
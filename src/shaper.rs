@@ -0,0 +1,162 @@
+#[derive(Debug)]
+pub(crate) struct Shaper {
+	altq_queues: Vec<Queue>,
+	limiters: Vec<Queue>,
+}
+
+/// An ALTQ queue, or a limiter or one of its queues
+#[derive(Debug)]
+pub(crate) struct Queue {
+	pub(crate) label: String,
+	source: Source,
+
+	/// Whether the queue exists in pfctl or dnctl's output
+	pub(crate) is_loaded: bool,
+
+	counters: crate::ssh_exec::dnctl_pipe_show::Counters,
+
+	/// `None` if the queue wasn't loaded as of the previous update
+	counters_previous: Option<crate::ssh_exec::dnctl_pipe_show::Counters>,
+
+	/// The maximum number of packets that can be queued, for ALTQ queues
+	pub(crate) limit: Option<u64>,
+}
+
+#[derive(Debug)]
+enum Source {
+	Altq { interface: String, name: String },
+
+	/// A limiter is fed both by its own queue and by its child queues.
+	Pipe { number: u32, queue_numbers: Vec<u32> },
+
+	Queue { number: u32 },
+}
+
+impl Shaper {
+	pub(crate) fn new(
+		shaper_queues: impl IntoIterator<Item = crate::pfconfig::ShaperQueue>,
+		limiters: impl IntoIterator<Item = crate::pfconfig::Limiter>,
+		interface_labels: &crate::interface::Labels,
+	) -> Self {
+		let new_queue = |label, source| Queue {
+			label,
+			source,
+			is_loaded: false,
+			counters: Default::default(),
+			counters_previous: None,
+			limit: None,
+		};
+
+		let altq_queues =
+			shaper_queues.into_iter()
+			.map(|crate::pfconfig::ShaperQueue { interface, name, description }| {
+				let label = format!("{} on {}", description.as_ref().unwrap_or(&name), interface_labels.get(&interface));
+				new_queue(label, Source::Altq { interface, name })
+			})
+			.collect();
+
+		let mut limiter_queues = vec![];
+		for crate::pfconfig::Limiter { name, number, queues } in limiters {
+			let queue_numbers = queues.iter().map(|&(_, number)| number).collect();
+			limiter_queues.push(new_queue(name.clone(), Source::Pipe { number, queue_numbers }));
+
+			for (queue_name, queue_number) in queues {
+				limiter_queues.push(new_queue(format!("{} / {}", name, queue_name), Source::Queue { number: queue_number }));
+			}
+		}
+
+		Shaper {
+			altq_queues,
+			limiters: limiter_queues,
+		}
+	}
+
+	pub(crate) fn altq_queues(&self) -> &[Queue] {
+		&self.altq_queues
+	}
+
+	pub(crate) fn limiters(&self) -> &[Queue] {
+		&self.limiters
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		if !self.altq_queues.is_empty() {
+			let altq_queues = crate::ssh_exec::pfctl_vs_queue::get_queues(session)?;
+
+			for queue in &mut self.altq_queues {
+				let Source::Altq { interface, name } = &queue.source else {
+					continue;
+				};
+
+				queue.counters_previous = queue.is_loaded.then_some(queue.counters);
+
+				let altq_queue = altq_queues.iter().find(|altq_queue| altq_queue.interface == *interface && altq_queue.name == *name);
+				queue.is_loaded = altq_queue.is_some();
+				queue.counters = altq_queue.map_or_else(Default::default, |altq_queue| crate::ssh_exec::dnctl_pipe_show::Counters {
+					packets: altq_queue.packets,
+					bytes: altq_queue.bytes,
+					dropped_packets: altq_queue.dropped_packets,
+					queued_packets: altq_queue.length,
+				});
+				queue.limit = altq_queue.map(|altq_queue| altq_queue.limit);
+			}
+		}
+
+		if !self.limiters.is_empty() {
+			let pipes = crate::ssh_exec::dnctl_pipe_show::get_pipes(session)?;
+			let dnctl_queues = crate::ssh_exec::dnctl_queue_show::get_queues(session)?;
+
+			let find_queue = |number: u32| dnctl_queues.iter().find(|&&(queue_number, _)| queue_number == number).map(|&(_, counters)| counters);
+
+			for queue in &mut self.limiters {
+				queue.counters_previous = queue.is_loaded.then_some(queue.counters);
+
+				let counters = match &queue.source {
+					Source::Altq { .. } => continue,
+
+					Source::Pipe { number, queue_numbers } => pipes.iter().find(|&&(pipe_number, _)| pipe_number == *number).map(|&(_, counters)| {
+						let mut counters = counters;
+						for &queue_number in queue_numbers {
+							if let Some(queue_counters) = find_queue(queue_number) {
+								counters += queue_counters;
+							}
+						}
+						counters
+					}),
+
+					Source::Queue { number } => find_queue(*number),
+				};
+
+				queue.is_loaded = counters.is_some();
+				queue.counters = counters.unwrap_or_default();
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Queue {
+	/// Bits, packets and dropped packets per second
+	pub(crate) fn rates(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32, f32)> {
+		if !self.is_loaded {
+			return None;
+		}
+
+		let counters_previous = self.counters_previous?;
+
+		Some((
+			crate::per_second(self.counters.bytes, counters_previous.bytes, time_since_previous) * 8.,
+			crate::per_second(self.counters.packets, counters_previous.packets, time_since_previous),
+			crate::per_second(self.counters.dropped_packets, counters_previous.dropped_packets, time_since_previous),
+		))
+	}
+
+	pub(crate) fn dropped_packets(&self) -> u64 {
+		self.counters.dropped_packets
+	}
+
+	pub(crate) fn queued_packets(&self) -> u64 {
+		self.counters.queued_packets
+	}
+}
@@ -1,9 +1,41 @@
+pub(crate) mod arp_an {
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct Output {
+		arp: Arp,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct Arp {
+		#[serde(rename = "arp-cache", default)]
+		arp_cache: Vec<Entry>,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	pub(crate) struct Entry {
+		/// `None` for incomplete entries
+		#[serde(rename = "mac-address", default)]
+		pub(crate) mac_address: Option<String>,
+
+		pub(crate) interface: String,
+	}
+
+	pub(crate) fn get_entries(session: &ssh2::Session) -> Result<Vec<Entry>, crate::Error> {
+		let Output { arp: Arp { arp_cache } } = super::exec_json(session, "/usr/sbin/arp -an --libxo json")?;
+		Ok(arp_cache)
+	}
+}
+
 pub(crate) mod batched_sysctls_1 {
-	pub(crate) fn run(session: &ssh2::Session) -> Result<(crate::boot_time::BootTime, crate::memory::Memory), crate::Error> {
-		let mut reader = super::exec_reader(session, "/sbin/sysctl -b kern.boottime hw.physmem vm.stats.vm.v_page_count")?;
+	pub(crate) fn run(session: &ssh2::Session) -> Result<(crate::boot_time::BootTime, crate::memory::Memory, crate::c_uint, String), crate::Error> {
+		// hw.model is variable length, so it has to be at the end
+		let mut reader = super::exec_reader(session, "/sbin/sysctl -b kern.boottime hw.physmem vm.stats.vm.v_page_count hw.pagesize hw.ncpu hw.model")?;
 		let boot_time = crate::Parse::parse(&mut reader)?;
 		let memory = crate::Parse::parse(&mut reader)?;
-		Ok((boot_time, memory))
+		let num_cpus = crate::Parse::parse(&mut reader)?;
+		let mut cpu_model = vec![];
+		let _ = std::io::Read::read_to_end(&mut reader, &mut cpu_model)?;
+		let cpu_model = String::from_utf8_lossy(&cpu_model).trim_end_matches('\0').to_owned();
+		Ok((boot_time, memory, num_cpus, cpu_model))
 	}
 }
 
@@ -14,25 +46,51 @@ pub(crate) mod batched_sysctls_2 {
 	}
 
 	impl Exec {
-		pub(crate) fn new(temperature_sysctls: &[crate::temperature_sysctl::TemperatureSysctl]) -> Self {
-			let mut command = "/sbin/sysctl -b vm.stats.vm.v_inactive_count vm.stats.vm.v_cache_count vm.stats.vm.v_free_count".to_owned();
+		pub(crate) fn new(
+			memory: &crate::memory::Memory,
+			cpu_frequency: &crate::cpu_frequency::CpuFrequency,
+			temperature_sysctls: &[crate::temperature_sysctl::TemperatureSysctl],
+		) -> Self {
+			let mut command =
+				"/sbin/sysctl -b \
+					vm.stats.vm.v_inactive_count vm.stats.vm.v_cache_count vm.stats.vm.v_free_count \
+					vm.stats.vm.v_wire_count vm.stats.vm.v_active_count"
+				.to_owned();
+
+			if memory.has_laundry {
+				command.push_str(" vm.stats.vm.v_laundry_count");
+			}
+
+			if memory.has_arc {
+				command.push_str(" kstat.zfs.misc.arcstats.size kstat.zfs.misc.arcstats.c_max kstat.zfs.misc.arcstats.hits kstat.zfs.misc.arcstats.misses");
+			}
+
+			command.push_str(" vm.loadavg");
+
+			if cpu_frequency.exists {
+				command.push_str(" dev.cpu.0.freq");
+			}
 
 			for temperature_sysctl in &temperature_sysctls[..] {
 				command.push_str(&format!(" '{}'", temperature_sysctl.name));
 			}
 
-			// kern.cp_time is variable length, so it has to be at the end
-			command.push_str(" kern.cp_time");
+			// kern.cp_times is variable length, so it has to be at the end
+			command.push_str(" kern.cp_time kern.cp_times");
 
 			Exec {
 				command,
 			}
 		}
 
+		#[allow(clippy::too_many_arguments)]
 		pub(crate) fn run(
 			&self,
 			cpu: &mut crate::cpu::Cpu,
 			memory: &mut crate::memory::Memory,
+			arc_stats: &mut crate::zfs::ArcStats,
+			load_average: &mut crate::load_average::LoadAverage,
+			cpu_frequency: &mut crate::cpu_frequency::CpuFrequency,
 			temperature_sysctls: &mut [crate::temperature_sysctl::TemperatureSysctl],
 			session: &ssh2::Session,
 		) -> Result<(), crate::Error> {
@@ -40,6 +98,14 @@ pub(crate) mod batched_sysctls_2 {
 
 			memory.update(&mut reader)?;
 
+			if memory.has_arc {
+				arc_stats.update(&mut reader)?;
+			}
+
+			load_average.update(&mut reader)?;
+
+			cpu_frequency.update(&mut reader)?;
+
 			for temperature_sysctl in temperature_sysctls {
 				temperature_sysctl.update(&mut reader)?;
 			}
@@ -58,6 +124,31 @@ pub(crate) mod clog_filter_log {
 }
 
 pub(crate) mod df {
+	#[derive(Debug)]
+	pub(crate) struct Exec {
+		command: String,
+	}
+
+	impl Exec {
+		pub(crate) fn new(filesystem_types: &[String]) -> Self {
+			let command =
+				if filesystem_types.is_empty() {
+					"/bin/df -ki --libxo json".to_owned()
+				}
+				else {
+					format!("/bin/df -ki -t '{}' --libxo json", filesystem_types.join(","))
+				};
+			Exec {
+				command,
+			}
+		}
+
+		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<Vec<Filesystem>, crate::Error> {
+			let Output { storage_system_information: StorageSystemInformation { filesystem } } = super::exec_json(session, &self.command)?;
+			Ok(filesystem)
+		}
+	}
+
 	#[derive(Debug, serde_derive::Deserialize)]
 	struct Output {
 		#[serde(rename = "storage-system-information")]
@@ -77,11 +168,242 @@ pub(crate) mod df {
 		pub(crate) total_blocks: u64,
 		#[serde(rename = "used-blocks")]
 		pub(crate) used_blocks: u64,
+		#[serde(rename = "used-inodes")]
+		pub(crate) used_inodes: u64,
+		#[serde(rename = "free-inodes")]
+		pub(crate) free_inodes: u64,
 	}
+}
+
+pub(crate) mod dhcpd_leases {
+	#[derive(Debug)]
+	pub(crate) struct Lease {
+		pub(crate) address: String,
+
+		/// Lowercased. `None` for IPv6 leases, which are keyed by DUID instead
+		pub(crate) mac_address: Option<String>,
 
-	pub(crate) fn get_filesystems(session: &ssh2::Session) -> Result<Vec<Filesystem>, crate::Error> {
-		let Output { storage_system_information: StorageSystemInformation { filesystem } } = super::exec_json(session, "/bin/df -kt ufs --libxo json")?;
-		Ok(filesystem)
+		pub(crate) hostname: Option<String>,
+
+		/// Like `active`, `expired` or `free`
+		pub(crate) binding_state: String,
+
+		/// `None` if the lease never ends or has already ended
+		pub(crate) expires_in: Option<std::time::Duration>,
+	}
+
+	/// Parses an ISC dhcpd leases file, like `/var/dhcpd/var/db/dhcpd.leases` or `/var/dhcpd/var/db/dhcpd6.leases`.
+	///
+	/// Returns an empty list if the file doesn't exist.
+	pub(crate) fn get_leases(path: &str, session: &ssh2::Session) -> Result<Vec<Lease>, crate::Error> {
+		// Lease end times are absolute, so also get the current time of the remote to not be affected by clock skew.
+		let mut lines = super::exec_lines(session, &format!("/bin/date +%s && [ -f '{0}' ] && /bin/cat '{0}'", path));
+
+		let now: u64 = match lines.next().transpose()? {
+			Some(now) => now.trim().parse()?,
+			None => return Ok(vec![]),
+		};
+
+		// The file is a journal, so later entries for an address supersede earlier ones.
+		let mut result: std::collections::BTreeMap<String, Lease> = Default::default();
+
+		let mut current = None;
+
+		for line in lines {
+			let line = line?;
+			let line = line.trim();
+
+			// `lease 192.168.1.100 {`, or `iaaddr 2001:db8::100 {` nested inside an `ia-na` block
+			if let Some(rest) = line.strip_suffix(" {") {
+				let mut parts = rest.split(' ');
+				if let (Some("lease" | "iaaddr"), Some(address)) = (parts.next(), parts.next()) {
+					current = Some(Lease {
+						address: address.to_owned(),
+						mac_address: None,
+						hostname: None,
+						binding_state: String::new(),
+						expires_in: None,
+					});
+				}
+				continue;
+			}
+
+			if line == "}" {
+				if let Some(lease) = current.take() {
+					let _ = result.insert(lease.address.clone(), lease);
+				}
+				continue;
+			}
+
+			let Some(lease) = &mut current else {
+				continue;
+			};
+
+			// `ends epoch 1585224000; # Thu Mar 26 12:00:00 2020` has a trailing comment
+			let line = line.split_once(" #").map_or(line, |(line, _)| line);
+
+			let Some(statement) = line.strip_suffix(';') else {
+				continue;
+			};
+
+			if let Some(binding_state) = statement.strip_prefix("binding state ") {
+				binding_state.clone_into(&mut lease.binding_state);
+			}
+			else if let Some(mac_address) = statement.strip_prefix("hardware ethernet ") {
+				lease.mac_address = Some(mac_address.to_lowercase());
+			}
+			else if let Some(hostname) = statement.strip_prefix("client-hostname ") {
+				lease.hostname = Some(hostname.trim_matches('"').to_owned());
+			}
+			else if let Some(ends) = statement.strip_prefix("ends ") {
+				// `ends 4 2020/03/26 12:00:00` in UTC, `ends epoch 1585224000`, or `ends never`
+				lease.expires_in =
+					parse_time(ends)
+					.and_then(|ends| ends.checked_sub(now))
+					.map(std::time::Duration::from_secs);
+			}
+		}
+
+		Ok(result.into_values().collect())
+	}
+
+	// Parses a `4 2020/03/26 12:00:00` or `epoch 1585224000` timestamp into the number of seconds since the Unix epoch.
+	//
+	// Returns `None` for `never`.
+	fn parse_time(s: &str) -> Option<u64> {
+		if let Some(seconds) = s.strip_prefix("epoch ") {
+			return seconds.parse().ok();
+		}
+
+		let mut parts = s.split(' ');
+		let _weekday = parts.next()?;
+
+		let mut date = parts.next()?.split('/');
+		let year: u64 = date.next()?.parse().ok()?;
+		let month: u64 = date.next()?.parse().ok()?;
+		let day: u64 = date.next()?.parse().ok()?;
+
+		let mut time = parts.next()?.split(':');
+		let hour: u64 = time.next()?.parse().ok()?;
+		let minute: u64 = time.next()?.parse().ok()?;
+		let second: u64 = time.next()?.parse().ok()?;
+
+		// Ref: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+		let year = if month <= 2 { year - 1 } else { year };
+		let era = year / 400;
+		let year_of_era = year - era * 400;
+		let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+
+		Some(days * 86400 + hour * 3600 + minute * 60 + second)
+	}
+
+	#[cfg(test)]
+	mod tests {
+		#[test]
+		fn parse_time() {
+			assert_eq!(super::parse_time("4 2020/03/26 12:00:00"), Some(1_585_224_000));
+			assert_eq!(super::parse_time("epoch 1585224000"), Some(1_585_224_000));
+			assert_eq!(super::parse_time("4 1970/01/01 00:00:00"), Some(0));
+			assert_eq!(super::parse_time("never"), None);
+			assert_eq!(super::parse_time("epoch never"), None);
+		}
+	}
+}
+
+pub(crate) mod dnctl_pipe_show {
+	#[derive(Clone, Copy, Debug, Default)]
+	pub(crate) struct Counters {
+		pub(crate) packets: u64,
+		pub(crate) bytes: u64,
+		pub(crate) dropped_packets: u64,
+
+		/// Packets currently in the queue
+		pub(crate) queued_packets: u64,
+	}
+
+	impl std::ops::AddAssign for Counters {
+		fn add_assign(&mut self, other: Self) {
+			self.packets += other.packets;
+			self.bytes += other.bytes;
+			self.dropped_packets += other.dropped_packets;
+			self.queued_packets += other.queued_packets;
+		}
+	}
+
+	/// Returns each pipe's number and the counters of the flows of its own queue.
+	///
+	/// Returns an empty list if dnctl is not installed.
+	pub(crate) fn get_pipes(session: &ssh2::Session) -> Result<Vec<(u32, Counters)>, crate::Error> {
+		// 00001:  10.000 Mbit/s    0 ms burst 0
+		// q131073  50 sl. 1 flows (1 buckets) sched 65537 weight 0 lmax 0 pri 0 droptail
+		//  sched 65537 type FIFO flags 0x0 0 buckets 0 active
+		// BKT Prot ___Source IP/port____ ____Dest. IP/port____ Tot_pkt/bytes Pkt/Byte Drp
+		//   0 ip           0.0.0.0/0             0.0.0.0/0     4453   412368  0    0   0
+		parse(session, "[ -x /sbin/dnctl ] && /sbin/dnctl pipe show", |line| {
+			let number = line.strip_suffix(':').or_else(|| line.split(": ").next())?;
+			if number.len() != 5 {
+				return None;
+			}
+			number.parse().ok()
+		})
+	}
+
+	/// Parses the output of `dnctl pipe show` or `dnctl queue show`, using `parse_header` to find the lines that start a new pipe or queue.
+	pub(super) fn parse(
+		session: &ssh2::Session,
+		command: &str,
+		parse_header: impl Fn(&str) -> Option<u32>,
+	) -> Result<Vec<(u32, Counters)>, crate::Error> {
+		let mut result: Vec<(u32, Counters)> = vec![];
+
+		for line in super::exec_lines(session, command) {
+			let line = line?;
+
+			if let Some(number) = parse_header(&line) {
+				result.push((number, Default::default()));
+				continue;
+			}
+
+			let Some((_, counters)) = result.last_mut() else {
+				continue;
+			};
+
+			// Flow lines start with the bucket number, followed by the protocol, source, destination,
+			// total packets and bytes, currently queued packets and bytes, and dropped packets.
+			let words: Vec<_> = line.split_whitespace().collect();
+			if let [bucket, _, _, _, packets, bytes, queued_packets, _, dropped_packets] = words[..] {
+				if bucket.parse::<u64>().is_err() {
+					continue;
+				}
+
+				*counters += Counters {
+					packets: packets.parse()?,
+					bytes: bytes.parse()?,
+					dropped_packets: dropped_packets.parse()?,
+					queued_packets: queued_packets.parse()?,
+				};
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod dnctl_queue_show {
+	/// Returns each queue's number and the counters of its flows.
+	///
+	/// Returns an empty list if dnctl is not installed.
+	pub(crate) fn get_queues(session: &ssh2::Session) -> Result<Vec<(u32, super::dnctl_pipe_show::Counters)>, crate::Error> {
+		// q00001  50 sl. 1 flows (256 buckets) sched 1 weight 0 lmax 0 pri 0 droptail
+		//     mask:  0x00 0x00000000/0x0000 -> 0xffffff00/0x0000
+		// BKT Prot ___Source IP/port____ ____Dest. IP/port____ Tot_pkt/bytes Pkt/Byte Drp
+		//  42 ip           0.0.0.0/0       192.168.1.0/0     8826  9456321  0    0   0
+		super::dnctl_pipe_show::parse(session, "[ -x /sbin/dnctl ] && /sbin/dnctl queue show", |line| {
+			let number = line.split_whitespace().next()?.strip_prefix('q')?;
+			number.parse().ok()
+		})
 	}
 }
 
@@ -129,6 +451,31 @@ pub(crate) mod ifconfig {
 		command: String,
 	}
 
+	#[derive(Debug, Default)]
+	pub(crate) struct Link {
+		pub(crate) flags: Vec<String>,
+		pub(crate) mtu: Option<u32>,
+		pub(crate) mac_address: Option<String>,
+		pub(crate) media: Option<Media>,
+
+		/// Member ports of a LAGG interface, and whether each of them is active
+		pub(crate) lagg_ports: Vec<(String, bool)>,
+	}
+
+	#[derive(Debug)]
+	pub(crate) struct Media {
+		/// The active media subtype and options, like `1000baseT <full-duplex>`
+		pub(crate) description: String,
+		pub(crate) speed_mbps: Option<u64>,
+		pub(crate) duplex: Option<Duplex>,
+	}
+
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	pub(crate) enum Duplex {
+		Full,
+		Half,
+	}
+
 	impl Exec {
 		pub(crate) fn new(name: &str) -> Self {
 			let command = format!("/sbin/ifconfig '{}'", name);
@@ -137,30 +484,226 @@ pub(crate) mod ifconfig {
 			}
 		}
 
-		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<Option<String>, crate::Error> {
-			let status =
-				super::exec_lines(session, &self.command)
-				.find_map(|line| match line {
-					Ok(line) => {
-						let index = line.find("status:")?;
-						let value = line[(index + "status:".len())..].trim().to_owned();
-						Some(Ok(value))
-					},
-					Err(err) => Some(Err(err)),
-				})
-				.transpose()?;
+		/// Returns the interface's status if it isn't `active`, and its link properties.
+		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<(Option<String>, Link), crate::Error> {
+			let mut status = None;
+			let mut link: Link = Default::default();
+
+			// igb0: flags=8843<UP,BROADCAST,RUNNING,SIMPLEX,MULTICAST> metric 0 mtu 1500
+			// 	ether 00:11:22:33:44:55
+			// 	media: Ethernet autoselect (1000baseT <full-duplex>)
+			// 	status: active
+			for line in super::exec_lines(session, &self.command) {
+				let line = line?;
+
+				// Only the first line, since lines like `laggport: igb0 flags=1c<ACTIVE,COLLECTING,DISTRIBUTING>` have their own flags
+				if let Some(index) = line.find("flags=").filter(|_| !line.starts_with(char::is_whitespace)) {
+					let flags = &line[index..];
+					if let (Some(start), Some(end)) = (flags.find('<'), flags.find('>')) {
+						link.flags = flags[(start + 1)..end].split(',').filter(|flag| !flag.is_empty()).map(ToOwned::to_owned).collect();
+					}
+
+					let mut words = line.split_whitespace();
+					if words.any(|word| word == "mtu") {
+						link.mtu = words.next().map(str::parse).transpose()?;
+					}
+				}
+				else if let Some(value) = line.trim().strip_prefix("ether ") {
+					link.mac_address = Some(value.trim().to_owned());
+				}
+				else if let Some(value) = line.trim().strip_prefix("laggport:") {
+					// laggport: igb2 flags=1c<ACTIVE,COLLECTING,DISTRIBUTING>
+					let mut parts = value.split_whitespace();
+					let name = parts.next().ok_or("ifconfig laggport line is malformed")?;
+					let is_active = parts.next().is_some_and(|flags| flags.contains("ACTIVE"));
+					link.lagg_ports.push((name.to_owned(), is_active));
+				}
+				else if let Some(value) = line.trim().strip_prefix("media:") {
+					link.media = Some(Media::parse(value.trim()));
+				}
+				else if let Some(value) = line.trim().strip_prefix("status:") {
+					status = Some(value.trim().to_owned());
+				}
+			}
 
 			if status.as_ref().map(AsRef::as_ref) == Some("active") {
-				Ok(None)
+				Ok((None, link))
 			}
 			else {
-				Ok(status)
+				Ok((status, link))
+			}
+		}
+	}
+
+	impl Media {
+		fn parse(value: &str) -> Self {
+			// `Ethernet autoselect (1000baseT <full-duplex>)` or `Ethernet 1000baseT <full-duplex>`
+			let description =
+				if let (Some(start), Some(end)) = (value.find('('), value.rfind(')')) {
+					&value[(start + 1)..end]
+				}
+				else {
+					value.split_once(' ').map_or(value, |(_, description)| description)
+				};
+
+			// `10baseT/UTP`, `1000baseT`, `2500Base-T`, `10Gbase-T`, ...
+			let subtype = description.split_whitespace().next().unwrap_or_default();
+			let speed_mbps = subtype.to_ascii_lowercase().split_once("base").and_then(|(speed, _)| {
+				let (speed, multiplier) = speed.strip_suffix('g').map_or((speed, 1), |speed| (speed, 1000));
+				let speed: u64 = speed.parse().ok()?;
+				Some(speed * multiplier)
+			});
+
+			let duplex =
+				if description.contains("full-duplex") {
+					Some(Duplex::Full)
+				}
+				else if description.contains("half-duplex") {
+					Some(Duplex::Half)
+				}
+				else {
+					None
+				};
+
+			Media {
+				description: description.to_owned(),
+				speed_mbps,
+				duplex,
 			}
 		}
 	}
 }
 
-pub(crate) mod netstat_bin {
+pub(crate) mod ifconfig_a {
+	#[derive(Debug, Default)]
+	pub(crate) struct Output {
+		pub(crate) carp: Vec<Carp>,
+
+		/// `None` if there is no pfsync interface
+		pub(crate) pfsync: Option<Pfsync>,
+	}
+
+	#[derive(Debug)]
+	pub(crate) struct Carp {
+		pub(crate) interface: String,
+
+		/// Like `MASTER`, `BACKUP` or `INIT`
+		pub(crate) state: String,
+
+		pub(crate) vhid: u8,
+		pub(crate) advbase: u32,
+		pub(crate) advskew: u32,
+	}
+
+	#[derive(Debug, Default)]
+	pub(crate) struct Pfsync {
+		pub(crate) syncdev: Option<String>,
+		pub(crate) syncpeer: Option<String>,
+
+		/// Whether the bulk update from the peer has completed
+		pub(crate) syncok: bool,
+	}
+
+	/// Returns the CARP state of every interface, and the pfsync state.
+	pub(crate) fn run(session: &ssh2::Session) -> Result<Output, crate::Error> {
+		let mut result: Output = Default::default();
+
+		let mut interface = String::new();
+
+		// igb1: flags=8943<UP,BROADCAST,RUNNING,PROMISC,SIMPLEX,MULTICAST> metric 0 mtu 1500
+		// 	carp: MASTER vhid 1 advbase 1 advskew 0
+		// pfsync0: flags=41<UP,RUNNING> metric 0 mtu 1500
+		// 	pfsync: syncdev: igb2 syncpeer: 10.0.0.2 maxupd: 128 defer: off
+		// 	syncok: 1
+		for line in super::exec_lines(session, "/sbin/ifconfig -a") {
+			let line = line?;
+
+			if !line.starts_with(char::is_whitespace) {
+				if let Some((name, _)) = line.split_once(": ") {
+					name.clone_into(&mut interface);
+				}
+				continue;
+			}
+
+			let line = line.trim_start();
+
+			if let Some(carp) = line.strip_prefix("carp: ") {
+				let mut words = carp.split_whitespace();
+				let state = words.next().ok_or("carp line is truncated")?.to_owned();
+
+				let (mut vhid, mut advbase, mut advskew) = (None, None, None);
+				while let Some(word) = words.next() {
+					match word {
+						"vhid" => vhid = words.next().map(str::parse).transpose()?,
+						"advbase" => advbase = words.next().map(str::parse).transpose()?,
+						"advskew" => advskew = words.next().map(str::parse).transpose()?,
+						_ => (),
+					}
+				}
+
+				result.carp.push(Carp {
+					interface: interface.clone(),
+					state,
+					vhid: vhid.ok_or("carp line does not have vhid")?,
+					advbase: advbase.ok_or("carp line does not have advbase")?,
+					advskew: advskew.ok_or("carp line does not have advskew")?,
+				});
+			}
+			else if let Some(pfsync) = line.strip_prefix("pfsync: ") {
+				let mut words = pfsync.split_whitespace();
+				let pfsync = result.pfsync.get_or_insert_with(Default::default);
+				while let Some(word) = words.next() {
+					match word {
+						"syncdev:" => pfsync.syncdev = words.next().map(ToOwned::to_owned),
+						"syncpeer:" => pfsync.syncpeer = words.next().map(ToOwned::to_owned),
+						_ => (),
+					}
+				}
+			}
+			else if let Some(syncok) = line.strip_prefix("syncok: ") {
+				result.pfsync.get_or_insert_with(Default::default).syncok = syncok.trim() == "1";
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod ndp_an {
+	#[derive(Debug)]
+	pub(crate) struct Entry {
+		/// Without the scope of link-local addresses
+		pub(crate) ip_address: String,
+
+		/// `None` for incomplete entries
+		pub(crate) mac_address: Option<String>,
+
+		pub(crate) interface: String,
+	}
+
+	pub(crate) fn get_entries(session: &ssh2::Session) -> Result<Vec<Entry>, crate::Error> {
+		let mut result = vec![];
+
+		// Neighbor, Linklayer Address, Netif, Expire, S, Flags
+		for line in super::exec_lines(session, "/usr/sbin/ndp -an").skip(1) {
+			let line = line?;
+			let mut parts = line.split_whitespace();
+			let (Some(ip_address), Some(mac_address), Some(interface)) = (parts.next(), parts.next(), parts.next()) else {
+				continue;
+			};
+
+			result.push(Entry {
+				ip_address: ip_address.split('%').next().unwrap_or(ip_address).to_owned(),
+				mac_address: (mac_address != "(incomplete)").then(|| mac_address.to_owned()),
+				interface: interface.to_owned(),
+			});
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod netstat_bdin {
 	#[derive(Debug, serde_derive::Deserialize)]
 	struct Output {
 		statistics: Statistics,
@@ -176,14 +719,28 @@ pub(crate) mod netstat_bin {
 		pub(crate) name: String,
 		pub(crate) network: String,
 		pub(crate) address: String,
+		#[serde(rename = "received-packets")]
+		pub(crate) received_packets: u64,
 		#[serde(rename = "received-bytes")]
 		pub(crate) received_bytes: u64,
+		#[serde(rename = "sent-packets")]
+		pub(crate) sent_packets: u64,
 		#[serde(rename = "sent-bytes")]
 		pub(crate) sent_bytes: u64,
+
+		// Only present for link-layer rows
+		#[serde(rename = "received-errors", default)]
+		pub(crate) received_errors: u64,
+		#[serde(rename = "dropped-packets", default)]
+		pub(crate) dropped_packets: u64,
+		#[serde(rename = "send-errors", default)]
+		pub(crate) send_errors: u64,
+		#[serde(default)]
+		pub(crate) collisions: u64,
 	}
 
 	pub(crate) fn get_interfaces(session: &ssh2::Session) -> Result<Vec<Interface>, crate::Error> {
-		let Output { statistics: Statistics { interface } } = super::exec_json(session, "/usr/bin/netstat -bin --libxo json")?;
+		let Output { statistics: Statistics { interface } } = super::exec_json(session, "/usr/bin/netstat -bdin --libxo json")?;
 		Ok(interface)
 	}
 }
@@ -209,21 +766,494 @@ pub(crate) mod netstat_m {
 	}
 }
 
+pub(crate) mod netstat_rn {
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct Output {
+		statistics: Statistics,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct Statistics {
+		#[serde(rename = "route-information")]
+		route_information: RouteInformation,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct RouteInformation {
+		#[serde(rename = "route-table")]
+		route_table: RouteTable,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct RouteTable {
+		#[serde(rename = "rt-family")]
+		rt_family: Vec<RtFamily>,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	struct RtFamily {
+		#[serde(rename = "address-family")]
+		address_family: String,
+		#[serde(rename = "rt-entry", default)]
+		rt_entry: Vec<Route>,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	pub(crate) struct Route {
+		#[serde(skip)]
+		pub(crate) is_ipv6: bool,
+		pub(crate) destination: String,
+		pub(crate) gateway: String,
+		pub(crate) flags: String,
+		#[serde(rename = "interface-name")]
+		pub(crate) interface: String,
+	}
+
+	pub(crate) fn get_routes(session: &ssh2::Session) -> Result<Vec<Route>, crate::Error> {
+		let Output { statistics: Statistics { route_information: RouteInformation { route_table: RouteTable { rt_family } } } } =
+			super::exec_json(session, "/usr/bin/netstat -rn --libxo json")?;
+
+		let mut result = vec![];
+		for RtFamily { address_family, rt_entry } in rt_family {
+			let is_ipv6 = match &*address_family {
+				"Internet" => false,
+				"Internet6" => true,
+				_ => continue,
+			};
+			result.extend(rt_entry.into_iter().map(|route| Route { is_ipv6, ..route }));
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod openvpn_status {
+	#[derive(Debug)]
+	pub(crate) struct Exec {
+		command: String,
+	}
+
+	#[derive(Debug, Default)]
+	pub(crate) struct Status {
+		pub(crate) is_connected: bool,
+
+		/// Common names of the connected clients, if this is a server
+		pub(crate) clients: Vec<String>,
+
+		pub(crate) bytes_received: u64,
+		pub(crate) bytes_sent: u64,
+	}
+
+	impl Exec {
+		// Queries the management sockets of the given OpenVPN instances, like `server1` or `client2`, in one exec.
+		pub(crate) fn new<'a>(ids: impl IntoIterator<Item = &'a str>) -> Self {
+			let mut command = "for id in".to_owned();
+			for id in ids {
+				command.push_str(&format!(" '{}'", id));
+			}
+			command.push_str(
+				"; do \
+					echo \"# $id\"; \
+					[ -S \"/var/etc/openvpn/$id/sock\" ] && printf 'state\\nstatus 2\\nquit\\n' | /usr/bin/nc -U \"/var/etc/openvpn/$id/sock\"; \
+				done",
+			);
+
+			Exec {
+				command,
+			}
+		}
+
+		/// Returns the status of each instance. Instances that are not running are not in the map.
+		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<std::collections::BTreeMap<String, Status>, crate::Error> {
+			let mut result: std::collections::BTreeMap<_, Status> = Default::default();
+
+			let mut id = None;
+
+			for line in super::exec_lines(session, &self.command) {
+				let line = line?;
+
+				// Each instance's output is preceded by its ID
+				if let Some(line) = line.strip_prefix("# ") {
+					id = Some(line.to_owned());
+					continue;
+				}
+
+				let status = result.entry(id.clone().ok_or("openvpn status output is malformed")?).or_default();
+
+				// The management interface ends lines with CRLF
+				let line = line.trim_end_matches('\r');
+
+				let mut fields = line.split(',');
+				match (fields.next(), fields.next()) {
+					// Reply to `state`, like `1700000000,CONNECTED,SUCCESS,10.0.8.1,,,,`
+					(Some(time), Some(state)) if time.parse::<u64>().is_ok() =>
+						status.is_connected = state == "CONNECTED",
+
+					// Servers list each client, with bytes received and sent in the fifth and sixth columns after the common name
+					(Some("CLIENT_LIST"), Some(common_name)) => {
+						let mut fields = fields.skip(3);
+						let bytes_received: u64 = fields.next().ok_or("CLIENT_LIST row is truncated")?.parse()?;
+						let bytes_sent: u64 = fields.next().ok_or("CLIENT_LIST row is truncated")?.parse()?;
+						status.clients.push(common_name.to_owned());
+						status.bytes_received += bytes_received;
+						status.bytes_sent += bytes_sent;
+					},
+
+					// Clients report the bytes of the tunnel connection itself
+					(Some("TCP/UDP read bytes"), Some(value)) => status.bytes_received = value.parse()?,
+					(Some("TCP/UDP write bytes"), Some(value)) => status.bytes_sent = value.parse()?,
+
+					_ => (),
+				}
+			}
+
+			Ok(result)
+		}
+	}
+}
+
 pub(crate) mod pfctl_s_info {
-	pub(crate) fn get_states_used(session: &ssh2::Session) -> Result<f32, crate::Error> {
-		let states_used =
-			super::exec_lines(session, "/sbin/pfctl -s info")
+	#[derive(Debug, Default)]
+	pub(crate) struct Info {
+		pub(crate) current_entries: u64,
+		pub(crate) searches: u64,
+		pub(crate) inserts: u64,
+		pub(crate) removals: u64,
+
+		/// The `Counters` and `Limit Counters` sections, like `match`, `memory` or `state-limit`, in the order pfctl prints them
+		pub(crate) counters: Vec<(String, u64)>,
+	}
+
+	pub(crate) fn get_info(session: &ssh2::Session) -> Result<Info, crate::Error> {
+		let mut result: Info = Default::default();
+
+		// State Table                          Total             Rate
+		//   current entries                      412
+		//   searches                       123456789          475.2/s
+		// Counters
+		//   match                            1234567            4.7/s
+		let mut section = String::new();
+
+		for line in super::exec_lines(session, "/sbin/pfctl -s info") {
+			let line = line?;
+
+			if !line.starts_with(' ') {
+				line.split("  ").next().unwrap_or_default().trim().clone_into(&mut section);
+				continue;
+			}
+
+			// The name can have spaces in it, and is followed by the total and then the rate.
+			let words: Vec<_> = line.split_whitespace().collect();
+			let Some(name_len) = words.iter().position(|word| word.parse::<u64>().is_ok()) else {
+				continue;
+			};
+			let name = words[..name_len].join(" ");
+			let value: u64 = words[name_len].parse()?;
+
+			match (&*section, &*name) {
+				("State Table", "current entries") => result.current_entries = value,
+				("State Table", "searches") => result.searches = value,
+				("State Table", "inserts") => result.inserts = value,
+				("State Table", "removals") => result.removals = value,
+				("Counters" | "Limit Counters", _) => result.counters.push((name, value)),
+				_ => (),
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod pfctl_s_memory {
+	pub(crate) fn get_states_limit(session: &ssh2::Session) -> Result<u64, crate::Error> {
+		// states        hard limit   100000
+		let states_limit =
+			super::exec_lines(session, "/sbin/pfctl -s memory")
 			.find_map(|line| match line {
 				Ok(line) => {
-					let index = line.find("current entries")?;
-					let value = line[(index + "current entries".len())..].trim().to_owned();
+					let mut words = line.split_whitespace();
+					if words.next() != Some("states") {
+						return None;
+					}
+					let value = words.last()?.to_owned();
 					Some(Ok(value))
 				},
 				Err(err) => Some(Err(err)),
 			})
-			.ok_or("could not read state table size")??
+			.ok_or("could not read state table limit")??
 			.parse()?;
-		Ok(states_used)
+		Ok(states_limit)
+	}
+}
+
+pub(crate) mod pfctl_vs_queue {
+	#[derive(Debug)]
+	pub(crate) struct Queue {
+		pub(crate) name: String,
+		pub(crate) interface: String,
+		pub(crate) packets: u64,
+		pub(crate) bytes: u64,
+		pub(crate) dropped_packets: u64,
+		pub(crate) length: u64,
+		pub(crate) limit: u64,
+	}
+
+	/// Returns an empty list if ALTQ is not enabled.
+	pub(crate) fn get_queues(session: &ssh2::Session) -> Result<Vec<Queue>, crate::Error> {
+		let mut result: Vec<Queue> = vec![];
+
+		// queue  qACK on em0 bandwidth 200Kb qlimit 500
+		//   [ pkts:      12345  bytes:    1234567  dropped pkts:      0 bytes:      0 ]
+		//   [ qlength:   0/500 ]
+		for line in super::exec_lines(session, "/sbin/pfctl -vs queue 2>/dev/null") {
+			let line = line?;
+			let words: Vec<_> = line.split_whitespace().collect();
+
+			match words[..] {
+				["queue", name, "on", interface, ..] => result.push(Queue {
+					name: name.to_owned(),
+					interface: interface.to_owned(),
+					packets: 0,
+					bytes: 0,
+					dropped_packets: 0,
+					length: 0,
+					limit: 0,
+				}),
+
+				["[", "pkts:", packets, "bytes:", bytes, "dropped", "pkts:", dropped_packets, ..] => {
+					if let Some(queue) = result.last_mut() {
+						queue.packets = packets.parse()?;
+						queue.bytes = bytes.parse()?;
+						queue.dropped_packets = dropped_packets.parse()?;
+					}
+				},
+
+				["[", "qlength:", ..] => {
+					// The length and limit are separated by `/` with padding on either side, like `  0/ 50`
+					let qlength = line.split("qlength:").nth(1).and_then(|qlength| qlength.split(']').next()).unwrap_or_default();
+					let (length, limit) = qlength.split_once('/').ok_or("pfctl queue qlength is malformed")?;
+					if let Some(queue) = result.last_mut() {
+						queue.length = length.trim().parse()?;
+						queue.limit = limit.trim().parse()?;
+					}
+				},
+
+				_ => (),
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod pfctl_vs_states {
+	#[derive(Debug)]
+	pub(crate) struct State {
+		/// `all` for floating states
+		pub(crate) interface: String,
+
+		pub(crate) source: std::net::IpAddr,
+		pub(crate) destination: std::net::IpAddr,
+
+		/// Sum of both directions
+		pub(crate) bytes: u64,
+	}
+
+	pub(crate) fn get_states(session: &ssh2::Session) -> Result<Vec<State>, crate::Error> {
+		let mut result = vec![];
+
+		// all tcp 192.168.1.10:52345 -> 1.2.3.4:443       ESTABLISHED:ESTABLISHED
+		//    age 00:01:02, expires in 23:59:58, 12:34 pkts, 1234:5678 bytes, rule 10
+		// em0 tcp 203.0.113.5:52345 (192.168.1.10:52345) -> 1.2.3.4:443       ESTABLISHED:ESTABLISHED
+		// all udp 2001:db8::1[123] <- 2001:db8::5[123]       MULTIPLE:SINGLE
+		//
+		// Outbound states are printed as `source -> destination` and inbound ones as `destination <- source`.
+		// NATed states also have the address on the other side of the NAT in parentheses. This is the internal host's address
+		// for outbound NAT, and the router's address for port forwards.
+		let mut current = None;
+
+		for line in super::exec_lines(session, "/sbin/pfctl -vs states") {
+			let line = line?;
+
+			if !line.starts_with(char::is_whitespace) {
+				let words: Vec<_> = line.split_whitespace().collect();
+				let Some(arrow) = words.iter().position(|&word| word == "->" || word == "<-") else {
+					current = None;
+					continue;
+				};
+
+				let interface = words[0];
+				let is_outbound = words[arrow] == "->";
+				let left = if is_outbound { words[2..arrow].last() } else { words[2..arrow].first() };
+				let left = left.map(|address| parse_address(address.trim_matches(|c| c == '(' || c == ')'))).transpose()?;
+				let right = words.get(arrow + 1).map(|&address| parse_address(address)).transpose()?;
+				let (Some(left), Some(right)) = (left, right) else {
+					current = None;
+					continue;
+				};
+
+				let (source, destination) = if is_outbound { (left, right) } else { (right, left) };
+				current = Some(State {
+					interface: interface.to_owned(),
+					source,
+					destination,
+					bytes: 0,
+				});
+				continue;
+			}
+
+			// A state can have several continuation lines, like the `id: ... creatorid: ...` one, and only one of them has the byte counts.
+			let Some(bytes) = line.split(", ").find_map(|field| field.trim().strip_suffix(" bytes")) else {
+				continue;
+			};
+
+			if let Some(mut state) = current.take() {
+				for bytes in bytes.split(':') {
+					state.bytes += bytes.parse::<u64>()?;
+				}
+				result.push(state);
+			}
+		}
+
+		Ok(result)
+	}
+
+	// Parses an address with a port, like `192.168.1.10:52345` or `2001:db8::1[123]`
+	fn parse_address(s: &str) -> Result<std::net::IpAddr, crate::Error> {
+		let address =
+			if let Some((address, _)) = s.split_once('[') { address }
+			else if let Some((address, _)) = s.rsplit_once(':').filter(|(address, _)| !address.contains(':')) { address }
+			else { s };
+		Ok(address.parse().map_err(|err| format!("could not parse address {:?} of pfctl state: {}", s, err))?)
+	}
+}
+
+pub(crate) mod pfctl_vvs_rules {
+	#[derive(Debug)]
+	pub(crate) struct Rule {
+		/// The first label of the rule, if it has any
+		pub(crate) label: Option<String>,
+
+		pub(crate) ridentifier: Option<u32>,
+
+		pub(crate) evaluations: u64,
+		pub(crate) packets: u64,
+		pub(crate) bytes: u64,
+		pub(crate) states: u64,
+	}
+
+	pub(crate) fn get_rules(session: &ssh2::Session) -> Result<Vec<Rule>, crate::Error> {
+		let mut result: Vec<Rule> = vec![];
+
+		// @12 pass in quick on igb1 inet from 192.168.2.0/24 to any flags S/SA keep state label "USER_RULE: Allow LAN" ridentifier 1712345678
+		//   [ Evaluations: 12345     Packets: 6789      Bytes: 1234567     States: 12    ]
+		//   [ Inserted: uid 0 pid 12345 State Creations: 45    ]
+		for line in super::exec_lines(session, "/sbin/pfctl -vvs rules") {
+			let line = line?;
+
+			if line.starts_with('@') {
+				// The label can have spaces in it, and the ridentifier comes after it.
+				let (label, rest) =
+					match line.split_once(" label \"").and_then(|(_, rest)| rest.split_once('"')) {
+						Some((label, rest)) => (Some(label.to_owned()), rest),
+						None => (None, &*line),
+					};
+
+				let mut words = rest.split_whitespace();
+				let ridentifier =
+					if words.any(|word| word == "ridentifier") {
+						let ridentifier = words.next().ok_or("pfctl rule ridentifier is malformed")?;
+						Some(ridentifier.parse()?)
+					}
+					else {
+						None
+					};
+
+				result.push(Rule {
+					label,
+					ridentifier,
+					evaluations: 0,
+					packets: 0,
+					bytes: 0,
+					states: 0,
+				});
+
+				continue;
+			}
+
+			let words: Vec<_> = line.split_whitespace().collect();
+			if let ["[", "Evaluations:", evaluations, "Packets:", packets, "Bytes:", bytes, "States:", states, ..] = words[..] {
+				if let Some(rule) = result.last_mut() {
+					rule.evaluations = evaluations.parse()?;
+					rule.packets = packets.parse()?;
+					rule.bytes = bytes.parse()?;
+					rule.states = states.parse()?;
+				}
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod pfctl_vvs_tables {
+	#[derive(Debug)]
+	pub(crate) struct Table {
+		pub(crate) name: String,
+		pub(crate) addresses: u64,
+
+		/// The number of lookups in the table that found an address
+		pub(crate) matches: u64,
+
+		/// Packets in both directions that were blocked or passed by rules using the table
+		pub(crate) blocked_packets: u64,
+		pub(crate) passed_packets: u64,
+	}
+
+	pub(crate) fn get_tables(session: &ssh2::Session) -> Result<Vec<Table>, crate::Error> {
+		let mut result: Vec<Table> = vec![];
+
+		// --a-r-C	bogons
+		// 	Addresses:   1234
+		// 	Cleared:     Thu Mar 28 10:00:00 2024
+		// 	References:  [ Anchors: 0                  Rules: 2                  ]
+		// 	Evaluations: [ NoMatch: 12345              Match: 678                ]
+		// 	In/Block:    [ Packets: 12                 Bytes: 3456               ]
+		// 	In/Pass:     [ Packets: 0                  Bytes: 0                  ]
+		// 	In/XPass:    [ Packets: 0                  Bytes: 0                  ]
+		// 	Out/Block:   [ Packets: 0                  Bytes: 0                  ]
+		// 	Out/Pass:    [ Packets: 0                  Bytes: 0                  ]
+		// 	Out/XPass:   [ Packets: 0                  Bytes: 0                  ]
+		for line in super::exec_lines(session, "/sbin/pfctl -vvs Tables") {
+			let line = line?;
+
+			if !line.starts_with('\t') {
+				let name = line.split('\t').nth(1).ok_or("pfctl table is malformed")?;
+				result.push(Table {
+					name: name.to_owned(),
+					addresses: 0,
+					matches: 0,
+					blocked_packets: 0,
+					passed_packets: 0,
+				});
+				continue;
+			}
+
+			let Some(table) = result.last_mut() else {
+				continue;
+			};
+
+			let words: Vec<_> = line.split_whitespace().collect();
+			match words[..] {
+				["Addresses:", addresses] => table.addresses = addresses.parse()?,
+				["Evaluations:", "[", "NoMatch:", _, "Match:", matches, ..] => table.matches = matches.parse()?,
+				["In/Block:" | "Out/Block:", "[", "Packets:", packets, ..] => table.blocked_packets += packets.parse::<u64>()?,
+				["In/Pass:" | "Out/Pass:", "[", "Packets:", packets, ..] => table.passed_packets += packets.parse::<u64>()?,
+				_ => (),
+			}
+		}
+
+		Ok(result)
 	}
 }
 
@@ -255,6 +1285,31 @@ pub(crate) mod pgrep {
 	}
 }
 
+pub(crate) mod ps {
+	#[derive(Clone, Copy, Debug)]
+	pub(crate) struct ProcessCounts {
+		pub(crate) total: usize,
+		pub(crate) running: usize,
+	}
+
+	pub(crate) fn get_process_counts(session: &ssh2::Session) -> Result<ProcessCounts, crate::Error> {
+		let mut result = ProcessCounts {
+			total: 0,
+			running: 0,
+		};
+
+		for state in super::exec_lines(session, "/bin/ps -ax -o state=") {
+			let state = state?;
+			result.total += 1;
+			if state.starts_with('R') {
+				result.running += 1;
+			}
+		}
+
+		Ok(result)
+	}
+}
+
 pub(crate) mod smartctl_a {
 	#[derive(Debug)]
 	pub(crate) struct Exec {
@@ -269,38 +1324,217 @@ pub(crate) mod smartctl_a {
 			}
 		}
 
-		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<(bool, crate::c_uint), crate::Error> {
-			let Output { smart_status: SmartStatus { passed }, temperature: Temperature { current } } = super::exec_json(session, &self.command)?;
-			Ok((passed, current))
+		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<Output, crate::Error> {
+			super::exec_json(session, &self.command)
 		}
 	}
 
-	#[derive(Clone, Copy, Debug, Default, serde_derive::Deserialize)]
-	struct Output {
-		smart_status: SmartStatus,
-		temperature: Temperature,
+	#[derive(Debug, Default, serde_derive::Deserialize)]
+	pub(crate) struct Output {
+		#[serde(default)]
+		pub(crate) smart_status: Option<SmartStatus>,
+		#[serde(default)]
+		pub(crate) temperature: Option<Temperature>,
+		#[serde(default)]
+		pub(crate) power_on_time: Option<PowerOnTime>,
+		#[serde(default)]
+		pub(crate) ata_smart_attributes: Option<AtaSmartAttributes>,
+		#[serde(default)]
+		pub(crate) nvme_smart_health_information_log: Option<NvmeSmartHealthInformationLog>,
 	}
 
 	#[derive(Clone, Copy, Debug, Default, serde_derive::Deserialize)]
-	struct SmartStatus {
-		passed: bool,
+	pub(crate) struct SmartStatus {
+		pub(crate) passed: bool,
 	}
 
 	#[derive(Clone, Copy, Debug, Default, serde_derive::Deserialize)]
-	struct Temperature {
-		current: crate::c_uint,
+	pub(crate) struct Temperature {
+		pub(crate) current: crate::c_uint,
+	}
+
+	#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+	pub(crate) struct PowerOnTime {
+		pub(crate) hours: u64,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	pub(crate) struct AtaSmartAttributes {
+		pub(crate) table: Vec<AtaSmartAttribute>,
+	}
+
+	#[derive(Debug, serde_derive::Deserialize)]
+	pub(crate) struct AtaSmartAttribute {
+		pub(crate) id: u8,
+
+		/// Normalized value, usually counting down from 100 or 200
+		pub(crate) value: u8,
+
+		pub(crate) raw: AtaSmartAttributeRaw,
+	}
+
+	#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+	pub(crate) struct AtaSmartAttributeRaw {
+		pub(crate) value: u64,
+	}
+
+	#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+	pub(crate) struct NvmeSmartHealthInformationLog {
+		pub(crate) available_spare: u8,
+		pub(crate) available_spare_threshold: u8,
+		pub(crate) percentage_used: u8,
+		pub(crate) media_errors: u64,
 	}
 }
 
 pub(crate) mod smartctl_i {
 	#[derive(Debug, serde_derive::Deserialize)]
 	struct Output {
-		serial_number: String,
+		#[serde(default)]
+		serial_number: Option<String>,
+		#[serde(default)]
+		smart_support: Option<SmartSupport>,
+	}
+
+	#[derive(Clone, Copy, Debug, serde_derive::Deserialize)]
+	struct SmartSupport {
+		available: bool,
+		enabled: bool,
 	}
 
-	pub(crate) fn get_serial_number(name: &str, session: &ssh2::Session) -> Result<String, crate::Error> {
-		let Output { serial_number } = super::exec_json(session, &format!("/usr/local/sbin/smartctl -i --json=c '/dev/{}'", name))?;
-		Ok(serial_number)
+	#[derive(Debug)]
+	pub(crate) struct Info {
+		pub(crate) serial_number: Option<String>,
+		pub(crate) smart_available: bool,
+	}
+
+	/// Returns `None` if smartctl is not installed.
+	pub(crate) fn get_info(name: &str, session: &ssh2::Session) -> Result<Option<Info>, crate::Error> {
+		let mut reader = super::exec_reader(session, &format!("[ -x /usr/local/sbin/smartctl ] && /usr/local/sbin/smartctl -i --json=c '/dev/{}'", name))?;
+		let mut output = String::new();
+		let _ = std::io::Read::read_to_string(&mut reader, &mut output)?;
+		if output.trim().is_empty() {
+			return Ok(None);
+		}
+
+		let Output { serial_number, smart_support } = serde_json::from_str(&output)?;
+
+		// Devices that smartctl can't open at all, like memory disks, don't have a serial number.
+		// NVMe devices don't report `smart_support` since SMART is mandatory for them.
+		let smart_available =
+			serial_number.is_some() &&
+			smart_support.is_none_or(|SmartSupport { available, enabled }| available && enabled);
+
+		Ok(Some(Info {
+			serial_number,
+			smart_available,
+		}))
+	}
+}
+
+pub(crate) mod swanctl_list_sas {
+	#[derive(Debug)]
+	pub(crate) struct Sa {
+		/// The name of the connection, like `con1`
+		pub(crate) name: String,
+		pub(crate) is_established: bool,
+
+		/// Time since the IKE SA was established
+		pub(crate) established: Option<std::time::Duration>,
+
+		/// Sum of the bytes of the SA's child SAs
+		pub(crate) bytes_received: u64,
+		pub(crate) bytes_sent: u64,
+	}
+
+	/// Returns an empty list if strongSwan is not installed or not running.
+	pub(crate) fn get_sas(session: &ssh2::Session) -> Result<Vec<Sa>, crate::Error> {
+		let lines = super::exec_lines(session, "[ -x /usr/local/sbin/swanctl ] && /usr/local/sbin/swanctl --list-sas 2>/dev/null");
+
+		let mut result: Vec<Sa> = vec![];
+
+		for line in lines {
+			let line = line?;
+
+			if !line.starts_with(' ') {
+				// IKE SA, like `con1: #3, ESTABLISHED, IKEv2, ...`
+				let Some((name, _)) = line.split_once(": #") else {
+					continue;
+				};
+				let is_established = line.split(", ").nth(1) == Some("ESTABLISHED");
+				result.push(Sa {
+					name: name.to_owned(),
+					is_established,
+					established: None,
+					bytes_received: 0,
+					bytes_sent: 0,
+				});
+				continue;
+			}
+
+			let Some(sa) = result.last_mut() else {
+				continue;
+			};
+
+			let line = line.trim_start();
+
+			if let Some(rest) = line.strip_prefix("established ") {
+				// `established 1234s ago, rekeying in 5678s`
+				let seconds = rest.split('s').next().ok_or("swanctl established line is malformed")?;
+				sa.established = Some(std::time::Duration::from_secs(seconds.parse()?));
+			}
+			else if line.starts_with("in ") || line.starts_with("out ") {
+				// Child SA traffic, like `in  c1234567,  12345 bytes,   100 packets,     0s ago`
+				let bytes: u64 =
+					line.split(", ").nth(1)
+					.and_then(|bytes| bytes.trim().strip_suffix(" bytes"))
+					.ok_or("swanctl child SA traffic line is malformed")?
+					.parse()?;
+				if line.starts_with("in ") {
+					sa.bytes_received += bytes;
+				}
+				else {
+					sa.bytes_sent += bytes;
+				}
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod swapinfo {
+	#[derive(Clone, Copy, Debug, Default)]
+	pub(crate) struct Swap {
+		pub(crate) num_devices: usize,
+		pub(crate) total_kib: u64,
+		pub(crate) used_kib: u64,
+	}
+
+	pub(crate) fn get_swap(session: &ssh2::Session) -> Result<Swap, crate::Error> {
+		let mut result: Swap = Default::default();
+
+		// Device          1K-blocks     Used    Avail Capacity
+		// /dev/ada0p3       4194304        0  4194304     0%
+		for line in super::exec_lines(session, "/usr/sbin/swapinfo -k").skip(1) {
+			let line = line?;
+
+			let mut line_parts = line.split_whitespace();
+
+			let device = line_parts.next().ok_or("swapinfo output is malformed")?;
+			if device == "Total" {
+				continue;
+			}
+
+			let total_kib: u64 = line_parts.next().ok_or("swapinfo output is malformed")?.parse()?;
+			let used_kib: u64 = line_parts.next().ok_or("swapinfo output is malformed")?.parse()?;
+
+			result.num_devices += 1;
+			result.total_kib += total_kib;
+			result.used_kib += used_kib;
+		}
+
+		Ok(result)
 	}
 }
 
@@ -318,6 +1552,14 @@ pub(crate) mod sysctl_kern_disks {
 	}
 }
 
+#[allow(non_snake_case)]
+pub(crate) mod sysctl_N {
+	pub(crate) fn exists(name: &str, session: &ssh2::Session) -> Result<bool, crate::Error> {
+		let line = super::exec_line(session, &format!("/sbin/sysctl -N '{}' 2>/dev/null", name))?;
+		Ok(!line.is_empty())
+	}
+}
+
 #[allow(non_snake_case)]
 pub(crate) mod uname_m {
 	pub(crate) fn run(session: &ssh2::Session) -> Result<String, crate::Error> {
@@ -332,6 +1574,207 @@ pub(crate) mod uname_sr {
 	}
 }
 
+pub(crate) mod unbound_control_stats {
+	#[derive(Clone, Copy, Debug, Default)]
+	pub(crate) struct Stats {
+		pub(crate) queries: u64,
+		pub(crate) cache_hits: u64,
+
+		/// Queries that weren't answered from the cache and needed recursion
+		pub(crate) recursive_replies: u64,
+
+		/// The average time taken by `recursive_replies`, in seconds
+		pub(crate) recursion_time_avg: f64,
+
+		/// `None` if extended statistics are not enabled in the DNS resolver's advanced settings
+		pub(crate) servfail: Option<u64>,
+
+		/// Queries that were dropped because the request list was full
+		pub(crate) request_list_exceeded: u64,
+
+		/// Queries in the request list that were replaced by newer ones because it was full
+		pub(crate) request_list_overwritten: u64,
+	}
+
+	/// Returns `None` if unbound is not running.
+	pub(crate) fn get_stats(session: &ssh2::Session) -> Result<Option<Stats>, crate::Error> {
+		let lines = super::exec_lines(
+			session,
+			"[ -f /var/unbound/unbound.conf ] && /usr/local/sbin/unbound-control -c /var/unbound/unbound.conf stats_noreset 2>/dev/null",
+		);
+
+		let mut result = None;
+
+		// total.num.queries=12345
+		// total.num.cachehits=10234
+		// total.num.recursivereplies=2111
+		// total.recursion.time.avg=0.052174
+		// total.requestlist.exceeded=0
+		// num.answer.rcode.SERVFAIL=12
+		for line in lines {
+			let line = line?;
+			let Some((key, value)) = line.split_once('=') else {
+				continue;
+			};
+
+			let stats = result.get_or_insert_with(Stats::default);
+
+			match key {
+				"total.num.queries" => stats.queries = value.parse()?,
+				"total.num.cachehits" => stats.cache_hits = value.parse()?,
+				"total.num.recursivereplies" => stats.recursive_replies = value.parse()?,
+				"total.recursion.time.avg" => stats.recursion_time_avg = value.parse()?,
+				"num.answer.rcode.SERVFAIL" => stats.servfail = Some(value.parse()?),
+				"total.requestlist.exceeded" => stats.request_list_exceeded = value.parse()?,
+				"total.requestlist.overwritten" => stats.request_list_overwritten = value.parse()?,
+				_ => (),
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub(crate) mod wg_show_all_dump {
+	#[derive(Debug)]
+	pub(crate) struct Peer {
+		pub(crate) interface: String,
+
+		/// Time since the latest handshake, or `None` if there hasn't been one
+		pub(crate) latest_handshake: Option<std::time::Duration>,
+
+		pub(crate) bytes_received: u64,
+		pub(crate) bytes_sent: u64,
+	}
+
+	// Returns the names of the WireGuard interfaces that exist, and their peers.
+	pub(crate) fn get_peers(session: &ssh2::Session) -> Result<(Vec<String>, Vec<Peer>), crate::Error> {
+		// Handshake times are absolute, so also get the current time of the remote to not be affected by clock skew.
+		let mut lines = super::exec_lines(session, "[ -x /usr/local/bin/wg ] && /bin/date +%s && /usr/local/bin/wg show all dump");
+
+		let now: u64 = match lines.next().transpose()? {
+			Some(now) => now.trim().parse()?,
+			None => return Ok(Default::default()),
+		};
+
+		let mut interfaces = vec![];
+		let mut peers = vec![];
+
+		for line in lines {
+			let line = line?;
+			let fields: Vec<_> = line.split('\t').collect();
+			match fields[..] {
+				// interface, private key, public key, listen port, fwmark
+				[interface, _, _, _, _] => interfaces.push(interface.to_owned()),
+
+				// interface, public key, preshared key, endpoint, allowed IPs, latest handshake, bytes received, bytes sent, persistent keepalive
+				[interface, _, _, _, _, latest_handshake, bytes_received, bytes_sent, _] => {
+					let latest_handshake: u64 = latest_handshake.parse()?;
+					peers.push(Peer {
+						interface: interface.to_owned(),
+						latest_handshake: (latest_handshake > 0).then(|| std::time::Duration::from_secs(now.saturating_sub(latest_handshake))),
+						bytes_received: bytes_received.parse()?,
+						bytes_sent: bytes_sent.parse()?,
+					});
+				},
+
+				_ => return Err(format!("unexpected line in wg show all dump output: {:?}", line).into()),
+			}
+		}
+
+		Ok((interfaces, peers))
+	}
+}
+
+pub(crate) mod zfs_list {
+	#[derive(Debug)]
+	pub(crate) struct Dataset {
+		pub(crate) name: String,
+		pub(crate) used: u64,
+		pub(crate) available: u64,
+	}
+
+	/// Lists the root dataset of every pool.
+	pub(crate) fn get_root_datasets(session: &ssh2::Session) -> Result<Vec<Dataset>, crate::Error> {
+		super::exec_lines(session, "/sbin/zfs list -Hp -d 0 -o name,used,avail 2>/dev/null")
+			.map(|line| {
+				let line = line?;
+				let mut line_parts = line.split('\t');
+				let name = line_parts.next().ok_or("zfs list output is malformed")?.to_owned();
+				let used = line_parts.next().ok_or("zfs list output is malformed")?.parse()?;
+				let available = line_parts.next().ok_or("zfs list output is malformed")?.parse()?;
+				Ok(Dataset {
+					name,
+					used,
+					available,
+				})
+			})
+			.collect()
+	}
+}
+
+pub(crate) mod zpool_list {
+	#[derive(Debug)]
+	pub(crate) struct Pool {
+		pub(crate) name: String,
+		pub(crate) size: u64,
+		pub(crate) allocated: u64,
+		pub(crate) fragmentation_percent: Option<u64>,
+		pub(crate) health: String,
+	}
+
+	pub(crate) fn get_pools(session: &ssh2::Session) -> Result<Vec<Pool>, crate::Error> {
+		// Prints nothing if ZFS isn't loaded, or there are no pools.
+		super::exec_lines(session, "/sbin/zpool list -Hp -o name,size,alloc,frag,health 2>/dev/null")
+			.map(|line| {
+				let line = line?;
+				let mut line_parts = line.split('\t');
+				let name = line_parts.next().ok_or("zpool list output is malformed")?.to_owned();
+				let size = line_parts.next().ok_or("zpool list output is malformed")?.parse()?;
+				let allocated = line_parts.next().ok_or("zpool list output is malformed")?.parse()?;
+				let fragmentation_percent = line_parts.next().ok_or("zpool list output is malformed")?.trim_end_matches('%').parse().ok();
+				let health = line_parts.next().ok_or("zpool list output is malformed")?.to_owned();
+				Ok(Pool {
+					name,
+					size,
+					allocated,
+					fragmentation_percent,
+					health,
+				})
+			})
+			.collect()
+	}
+}
+
+pub(crate) mod zpool_status {
+	#[derive(Debug)]
+	pub(crate) struct Exec {
+		command: String,
+	}
+
+	impl Exec {
+		pub(crate) fn new(pool_name: &str) -> Self {
+			let command = format!("/sbin/zpool status '{}'", pool_name);
+			Exec {
+				command,
+			}
+		}
+
+		/// Returns the value of the `scan:` line, if any.
+		pub(crate) fn run(&self, session: &ssh2::Session) -> Result<Option<String>, crate::Error> {
+			super::exec_lines(session, &self.command)
+			.find_map(|line| match line {
+				Ok(line) => {
+					let value = line.trim_start().strip_prefix("scan:")?.trim().to_owned();
+					Some(Ok(value))
+				},
+				Err(err) => Some(Err(err)),
+			})
+			.transpose()
+		}
+	}
+}
+
 fn exec_reader(session: &ssh2::Session, command: &str) -> Result<ssh2::Channel, crate::Error> {
 	let mut channel = session.channel_session()?;
 	channel.exec(command)?;
@@ -0,0 +1,132 @@
+#[derive(Debug)]
+pub(crate) struct StateTable {
+	/// The hard limit on the number of states, from `set limit states` or pfSense's "Firewall Maximum States"
+	pub(crate) limit: u64,
+
+	/// `None` before the first update
+	info: Option<crate::ssh_exec::pfctl_s_info::Info>,
+	info_previous: Option<crate::ssh_exec::pfctl_s_info::Info>,
+
+	num_top_talkers: usize,
+
+	/// `None` if top talkers are not enabled
+	top_talkers: Option<TopTalkers>,
+}
+
+/// Per-second rates of state table operations
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Rates {
+	pub(crate) searches: f32,
+	pub(crate) inserts: f32,
+	pub(crate) removals: f32,
+	pub(crate) matches: f32,
+}
+
+#[derive(Debug)]
+pub(crate) struct TopTalkers {
+	pub(crate) sources: Vec<Talker>,
+	pub(crate) destinations: Vec<Talker>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Talker {
+	pub(crate) interface: String,
+	pub(crate) address: std::net::IpAddr,
+	pub(crate) num_states: usize,
+
+	/// Bytes passed by the talker's current states, in both directions
+	pub(crate) bytes: u64,
+}
+
+impl StateTable {
+	pub(crate) fn new(config: &crate::config::States) -> Self {
+		StateTable {
+			limit: 0,
+			info: None,
+			info_previous: None,
+			num_top_talkers: config.top_talkers,
+			top_talkers: None,
+		}
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		self.limit = crate::ssh_exec::pfctl_s_memory::get_states_limit(session)?;
+
+		let info = crate::ssh_exec::pfctl_s_info::get_info(session)?;
+		self.info_previous = self.info.replace(info);
+
+		if self.num_top_talkers > 0 {
+			let states = crate::ssh_exec::pfctl_vs_states::get_states(session)?;
+			self.top_talkers = Some(top_talkers(&states, self.num_top_talkers));
+		}
+
+		Ok(())
+	}
+
+	pub(crate) fn used(&self) -> u64 {
+		self.info.as_ref().map_or(0, |info| info.current_entries)
+	}
+
+	pub(crate) fn rates(&self, time_since_previous: std::time::Duration) -> Option<Rates> {
+		let info = self.info.as_ref()?;
+		let info_previous = self.info_previous.as_ref()?;
+
+		let matches = |info: &crate::ssh_exec::pfctl_s_info::Info| info.counters.iter().find(|(name, _)| name == "match").map_or(0, |&(_, value)| value);
+
+		Some(Rates {
+			searches: crate::per_second(info.searches, info_previous.searches, time_since_previous),
+			inserts: crate::per_second(info.inserts, info_previous.inserts, time_since_previous),
+			removals: crate::per_second(info.removals, info_previous.removals, time_since_previous),
+			matches: crate::per_second(matches(info), matches(info_previous), time_since_previous),
+		})
+	}
+
+	/// Counters of packets that were dropped or hit a limit, like `memory` or `state-limit`, and whether each of them increased since the previous update.
+	///
+	/// The `match` counter is not included since it counts rule matches rather than problems.
+	pub(crate) fn counters(&self) -> impl Iterator<Item = (&str, u64, bool)> {
+		self.info.iter().flat_map(|info| &info.counters)
+			.filter(|(name, _)| name != "match")
+			.map(move |(name, value)| {
+				let value_previous =
+					self.info_previous.as_ref()
+					.and_then(|info_previous| info_previous.counters.iter().find(|(name_previous, _)| name_previous == name))
+					.map_or(*value, |&(_, value_previous)| value_previous);
+				(&**name, *value, *value > value_previous)
+			})
+	}
+
+	/// The sources and destinations with the most bytes in their current states, or `None` if top talkers are not enabled.
+	pub(crate) fn top_talkers(&self) -> Option<&TopTalkers> {
+		self.top_talkers.as_ref()
+	}
+}
+
+fn top_talkers(states: &[crate::ssh_exec::pfctl_vs_states::State], num_top_talkers: usize) -> TopTalkers {
+	let top = |key: fn(&crate::ssh_exec::pfctl_vs_states::State) -> std::net::IpAddr| {
+		let mut talkers: std::collections::BTreeMap<(&str, std::net::IpAddr), (usize, u64)> = Default::default();
+		for state in states {
+			let (num_states, bytes) = talkers.entry((&state.interface, key(state))).or_default();
+			*num_states += 1;
+			*bytes += state.bytes;
+		}
+
+		let mut talkers: Vec<_> =
+			talkers.into_iter()
+			.map(|((interface, address), (num_states, bytes))| Talker {
+				interface: interface.to_owned(),
+				address,
+				num_states,
+				bytes,
+			})
+			.collect();
+		talkers.sort_by_key(|talker| std::cmp::Reverse((talker.bytes, talker.num_states)));
+		talkers.truncate(num_top_talkers);
+		talkers
+	};
+
+	TopTalkers {
+		sources: top(|state| state.source),
+		destinations: top(|state| state.destination),
+	}
+}
@@ -0,0 +1,237 @@
+// The largest TCP message that's accepted. filterlog messages are a few hundred bytes, so anything bigger than this is a misbehaving client.
+const MAX_TCP_MESSAGE_LEN: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub(crate) enum Receiver {
+	Tcp(std::net::TcpListener),
+	Udp(std::net::UdpSocket),
+}
+
+impl Receiver {
+	pub(crate) fn bind(config: &crate::config::Receive) -> Result<Self, crate::Error> {
+		let result = match config.protocol {
+			crate::config::SyslogProtocol::Tcp => std::net::TcpListener::bind(&config.address).map(Receiver::Tcp),
+			crate::config::SyslogProtocol::Udp => std::net::UdpSocket::bind(&config.address).map(Receiver::Udp),
+		};
+		let result = result.map_err(|err| format!("could not listen for syslog messages on {}: {}", config.address, err))?;
+		Ok(result)
+	}
+
+	/// Receives syslog messages forever, and calls `f` with each filterlog message converted to the format that `clog` prints.
+	pub(crate) fn run(self, mut f: impl FnMut(&str) -> Result<(), crate::Error>) -> Result<(), crate::Error> {
+		match self {
+			Receiver::Tcp(listener) => {
+				// Each connection is read on its own thread so that one client can't hold up the others,
+				// and the lines are sent back to this thread so that `f` is only ever called from one thread.
+				let (lines_sender, lines_receiver) = std::sync::mpsc::sync_channel(1024);
+
+				let _ = std::thread::spawn(move || loop {
+					let Ok((stream, _)) = listener.accept() else {
+						// Accepting can fail because of a client that reset the connection, or running out of file descriptors.
+						// Neither is a reason to stop listening, but don't spin in the latter case.
+						std::thread::sleep(std::time::Duration::from_millis(100));
+						continue;
+					};
+
+					let lines_sender = lines_sender.clone();
+					let _ = std::thread::spawn(move || {
+						// A client that sends something malformed, or that resets the connection, only loses its own connection.
+						let mut stream = std::io::BufReader::new(stream);
+						while let Ok(Some(message)) = read_tcp_message(&mut stream) {
+							if let Some(line) = to_clog_line(&message) {
+								if lines_sender.send(line).is_err() {
+									break;
+								}
+							}
+						}
+					});
+				});
+
+				for line in lines_receiver {
+					f(&line)?;
+				}
+
+				Ok(())
+			},
+
+			Receiver::Udp(socket) => {
+				let mut buf = vec![0_u8; 65536];
+				loop {
+					let (len, _) = socket.recv_from(&mut buf)?;
+					let message = String::from_utf8_lossy(&buf[..len]);
+					if let Some(line) = to_clog_line(&message) {
+						f(&line)?;
+					}
+				}
+			},
+		}
+	}
+}
+
+// Handles both octet-counting and newline-delimited framing (RFC 6587). Returns `None` when the connection is closed.
+fn read_tcp_message(reader: &mut impl std::io::BufRead) -> Result<Option<String>, crate::Error> {
+	let message = match reader.fill_buf()?.first().copied() {
+		Some(first) if first.is_ascii_digit() => {
+			// The length is at most as many digits as `MAX_TCP_MESSAGE_LEN`, plus the space after it.
+			let mut len = vec![];
+			let _ = std::io::BufRead::read_until(&mut std::io::Read::take(&mut *reader, 7), b' ', &mut len)?;
+			let len: usize = std::str::from_utf8(&len)?.trim_end().parse()?;
+			if len > MAX_TCP_MESSAGE_LEN {
+				return Err(format!("syslog message of {} bytes is too large", len).into());
+			}
+
+			let mut message = vec![0_u8; len];
+			let () = reader.read_exact(&mut message)?;
+			message
+		},
+
+		Some(_) => {
+			let mut message = vec![];
+			let _ = std::io::BufRead::read_until(&mut std::io::Read::take(&mut *reader, MAX_TCP_MESSAGE_LEN as u64), b'\n', &mut message)?;
+			if message.last() != Some(&b'\n') && message.len() == MAX_TCP_MESSAGE_LEN {
+				return Err("syslog message is too large".into());
+			}
+			message
+		},
+
+		None => return Ok(None),
+	};
+
+	Ok(Some(String::from_utf8_lossy(&message).into_owned()))
+}
+
+/// Converts an RFC 3164 or RFC 5424 syslog message from filterlog into the `MMM dd HH:mm:ss host filterlog: ...` format.
+///
+/// Returns `None` if the message is malformed or is not from filterlog.
+fn to_clog_line(message: &str) -> Option<String> {
+	let message = message.trim_end_matches(['\r', '\n']);
+
+	// <PRI>
+	let message = message.strip_prefix('<')?;
+	let message = &message[message.find('>')? + 1..];
+
+	if let Some(message) = message.strip_prefix("1 ") {
+		// RFC 5424: VERSION SP TIMESTAMP SP HOSTNAME SP APP-NAME SP PROCID SP MSGID SP STRUCTURED-DATA [SP MSG]
+		let mut parts = message.splitn(6, ' ');
+		let timestamp = parts.next()?;
+		let hostname = parts.next()?;
+		let app_name = parts.next()?;
+		let _procid = parts.next()?;
+		let _msgid = parts.next()?;
+		let rest = parts.next()?;
+
+		if app_name != "filterlog" {
+			return None;
+		}
+
+		// Skip STRUCTURED-DATA, which is either NILVALUE or a sequence of [...] elements.
+		let msg =
+			if let Some(rest) = rest.strip_prefix('-') {
+				rest
+			}
+			else {
+				let mut rest = rest;
+				while rest.starts_with('[') {
+					rest = &rest[sd_element_len(rest)?..];
+				}
+				rest
+			};
+		let msg = msg.trim_start();
+
+		// YYYY-MM-DDTHH:MM:SS[.frac](Z|+HH:MM) -> MMM dd HH:mm:ss
+		let month: usize = timestamp.get(5..7)?.parse().ok()?;
+		let month = *["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"].get(month.checked_sub(1)?)?;
+		let day: u8 = timestamp.get(8..10)?.parse().ok()?;
+		let time = timestamp.get(11..19)?;
+
+		Some(format!("{} {:2} {} {} filterlog: {}", month, day, time, hostname, msg))
+	}
+	else {
+		// RFC 3164: TIMESTAMP SP [HOSTNAME SP] TAG: MSG
+		//
+		// The timestamp is already in the right format.
+		let tag_end = message.find(": ")?;
+		if !message[..tag_end].contains("filterlog") {
+			return None;
+		}
+
+		Some(message.to_owned())
+	}
+}
+
+/// Returns the length of the SD-ELEMENT at the start of `s`, taking into account that param values can contain escaped `]`
+fn sd_element_len(s: &str) -> Option<usize> {
+	let mut is_in_value = false;
+	let mut is_escaped = false;
+	for (i, c) in s.char_indices() {
+		match c {
+			_ if is_escaped => is_escaped = false,
+			'\\' if is_in_value => is_escaped = true,
+			'"' => is_in_value = !is_in_value,
+			']' if !is_in_value => return Some(i + 1),
+			_ => (),
+		}
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn read_tcp_message_octet_counting() {
+		let mut reader = &b"11 <13>1 hello5 world"[..];
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap().as_deref(), Some("<13>1 hello"));
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap().as_deref(), Some("world"));
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap(), None);
+	}
+
+	#[test]
+	fn read_tcp_message_newline_delimited() {
+		let mut reader = &b"<13>first\n<13>second\n"[..];
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap().as_deref(), Some("<13>first\n"));
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap().as_deref(), Some("<13>second\n"));
+		assert_eq!(super::read_tcp_message(&mut reader).unwrap(), None);
+	}
+
+	#[test]
+	fn read_tcp_message_too_large() {
+		let mut reader = &b"65537 <13>"[..];
+		assert!(super::read_tcp_message(&mut reader).is_err());
+
+		// A length with more digits than fit in the cap isn't read past the cap either
+		let mut reader = &b"1000000000 <13>"[..];
+		assert!(super::read_tcp_message(&mut reader).is_err());
+
+		let message = vec![b'<'; super::MAX_TCP_MESSAGE_LEN + 1];
+		let mut reader = &message[..];
+		assert!(super::read_tcp_message(&mut reader).is_err());
+	}
+
+	#[test]
+	fn to_clog_line_rfc3164() {
+		assert_eq!(
+			super::to_clog_line("<134>Oct  9 06:15:32 pfsense filterlog[1234]: 5,,,1000000103,igb0,match,block,in,4\n").as_deref(),
+			Some("Oct  9 06:15:32 pfsense filterlog[1234]: 5,,,1000000103,igb0,match,block,in,4"),
+		);
+	}
+
+	#[test]
+	fn to_clog_line_rfc5424() {
+		assert_eq!(
+			super::to_clog_line("<134>1 2024-10-09T06:15:32.123456+00:00 pfsense filterlog 1234 - - 5,,,1000000103,igb0,match,block,in,4").as_deref(),
+			Some("Oct  9 06:15:32 pfsense filterlog: 5,,,1000000103,igb0,match,block,in,4"),
+		);
+
+		assert_eq!(
+			super::to_clog_line(r#"<134>1 2024-12-19T06:15:32Z pfsense filterlog 1234 - [a b="x\]"][c d="]"] 5,,,1000000103,igb0"#).as_deref(),
+			Some("Dec 19 06:15:32 pfsense filterlog: 5,,,1000000103,igb0"),
+		);
+	}
+
+	#[test]
+	fn to_clog_line_not_filterlog() {
+		assert_eq!(super::to_clog_line("<134>1 2024-10-09T06:15:32Z pfsense sshd 1234 - - Accepted publickey"), None);
+		assert_eq!(super::to_clog_line("<134>Oct  9 06:15:32 pfsense sshd[1234]: Accepted publickey"), None);
+	}
+}
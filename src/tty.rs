@@ -0,0 +1,172 @@
+pub(crate) fn num_rows() -> Option<usize> {
+	unsafe {
+		let mut winsize: libc::winsize = std::mem::zeroed();
+		if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, std::ptr::addr_of_mut!(winsize)) != 0 || winsize.ws_row == 0 {
+			return None;
+		}
+
+		Some(winsize.ws_row.into())
+	}
+}
+
+/// Puts stdin into non-canonical, no-echo mode so that individual keypresses can be read.
+///
+/// Signal generation is also disabled, so Ctrl-C arrives as [`Key::Interrupt`] and the terminal can be restored before exiting.
+/// The original mode is restored when this is dropped.
+pub(crate) struct RawMode {
+	original: OriginalMode,
+}
+
+/// The terminal mode from before [`RawMode`] was enabled, for threads that exit the process and so can't rely on `RawMode` being dropped
+#[derive(Clone, Copy)]
+pub(crate) struct OriginalMode(libc::termios);
+
+impl RawMode {
+	/// Returns `None` if stdin is not a terminal.
+	pub(crate) fn enable() -> Result<Option<Self>, crate::Error> {
+		unsafe {
+			if libc::isatty(libc::STDIN_FILENO) != 1 {
+				return Ok(None);
+			}
+
+			let mut original: libc::termios = std::mem::zeroed();
+			if libc::tcgetattr(libc::STDIN_FILENO, std::ptr::addr_of_mut!(original)) != 0 {
+				return Err(std::io::Error::last_os_error().into());
+			}
+
+			let mut raw = original;
+			raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+			raw.c_cc[libc::VMIN] = 1;
+			raw.c_cc[libc::VTIME] = 0;
+			if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, std::ptr::addr_of!(raw)) != 0 {
+				return Err(std::io::Error::last_os_error().into());
+			}
+
+			Ok(Some(RawMode {
+				original: OriginalMode(original),
+			}))
+		}
+	}
+
+	pub(crate) fn original(&self) -> OriginalMode {
+		self.original
+	}
+}
+
+impl Drop for RawMode {
+	fn drop(&mut self) {
+		self.original.restore();
+	}
+}
+
+impl OriginalMode {
+	pub(crate) fn restore(&self) {
+		unsafe {
+			let _ = libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, std::ptr::addr_of!(self.0));
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Key {
+	Up,
+	Down,
+	PageUp,
+	PageDown,
+	Home,
+	End,
+	Enter,
+	Escape,
+	Backspace,
+	Interrupt,
+	Char(char),
+}
+
+pub(crate) fn keys() -> impl Iterator<Item = std::io::Result<Key>> {
+	std::iter::from_fn(|| loop {
+		let b = match read_byte() {
+			Ok(Some(b)) => b,
+			Ok(None) => return None,
+			Err(err) => return Some(Err(err)),
+		};
+
+		let key = match b {
+			0x03 => Key::Interrupt,
+			b'\r' | b'\n' => Key::Enter,
+			0x08 | 0x7F => Key::Backspace,
+
+			0x1B => {
+				// A lone Esc is indistinguishable from the start of an escape sequence,
+				// so give the rest of the sequence a moment to arrive.
+				if !stdin_readable(50) {
+					return Some(Ok(Key::Escape));
+				}
+
+				match read_byte() {
+					Ok(Some(b'[')) => (),
+					Ok(Some(_)) => return Some(Ok(Key::Escape)),
+					Ok(None) => return None,
+					Err(err) => return Some(Err(err)),
+				}
+
+				let mut sequence = vec![];
+				loop {
+					match read_byte() {
+						Ok(Some(b)) => {
+							sequence.push(b);
+							if (0x40..=0x7E).contains(&b) {
+								break;
+							}
+						},
+						Ok(None) => return None,
+						Err(err) => return Some(Err(err)),
+					}
+				}
+
+				match &sequence[..] {
+					b"A" => Key::Up,
+					b"B" => Key::Down,
+					b"5~" => Key::PageUp,
+					b"6~" => Key::PageDown,
+					b"H" | b"1~" => Key::Home,
+					b"F" | b"4~" => Key::End,
+					_ => continue,
+				}
+			},
+
+			b if b.is_ascii_graphic() || b == b' ' => Key::Char(b.into()),
+
+			_ => continue,
+		};
+
+		return Some(Ok(key));
+	})
+}
+
+// Reads from the fd directly rather than through `std::io::Stdin`, since the latter's buffering would hide
+// the remainder of an escape sequence from `stdin_readable`.
+fn read_byte() -> std::io::Result<Option<u8>> {
+	let mut b = 0_u8;
+	loop {
+		let result = unsafe { libc::read(libc::STDIN_FILENO, std::ptr::addr_of_mut!(b).cast(), 1) };
+		match result {
+			1 => return Ok(Some(b)),
+			0 => return Ok(None),
+			_ => {
+				let err = std::io::Error::last_os_error();
+				if err.kind() != std::io::ErrorKind::Interrupted {
+					return Err(err);
+				}
+			},
+		}
+	}
+}
+
+fn stdin_readable(timeout_ms: libc::c_int) -> bool {
+	let mut pollfd = libc::pollfd {
+		fd: libc::STDIN_FILENO,
+		events: libc::POLLIN,
+		revents: 0,
+	};
+	unsafe { libc::poll(std::ptr::addr_of_mut!(pollfd), 1, timeout_ms) > 0 }
+}
@@ -0,0 +1,95 @@
+#[derive(Debug)]
+pub(crate) struct Unbound {
+	/// Whether the DNS resolver is enabled in pfSense. unbound isn't queried if it isn't.
+	pub(crate) is_enabled: bool,
+
+	/// `None` if unbound is not running
+	stats: Option<crate::ssh_exec::unbound_control_stats::Stats>,
+
+	stats_previous: Option<crate::ssh_exec::unbound_control_stats::Stats>,
+}
+
+impl Unbound {
+	pub(crate) fn new(is_enabled: bool) -> Self {
+		Unbound {
+			is_enabled,
+			stats: None,
+			stats_previous: None,
+		}
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		if !self.is_enabled {
+			return Ok(());
+		}
+
+		let stats = crate::ssh_exec::unbound_control_stats::get_stats(session)?;
+		self.stats_previous = std::mem::replace(&mut self.stats, stats);
+		Ok(())
+	}
+
+	pub(crate) fn is_running(&self) -> bool {
+		self.stats.is_some()
+	}
+
+	pub(crate) fn query_rate(&self, time_since_previous: std::time::Duration) -> Option<f32> {
+		let (stats, stats_previous) = self.stats_and_previous()?;
+		Some(crate::per_second(stats.queries, stats_previous.queries, time_since_previous))
+	}
+
+	/// The percentage of queries that were answered from the cache since the previous update,
+	/// or since unbound was started if there weren't any queries since then.
+	pub(crate) fn cache_hit_percent(&self) -> Option<f32> {
+		let stats = self.stats.as_ref()?;
+
+		let (queries, cache_hits) = match self.stats_and_previous() {
+			Some((stats, stats_previous)) if stats.queries > stats_previous.queries =>
+				(stats.queries - stats_previous.queries, stats.cache_hits.saturating_sub(stats_previous.cache_hits)),
+			_ => (stats.queries, stats.cache_hits),
+		};
+
+		if queries == 0 {
+			return None;
+		}
+
+		Some(cache_hits as f32 * 100. / queries as f32)
+	}
+
+	/// The average time taken by queries that needed recursion since the previous update,
+	/// or since unbound was started if there weren't any such queries since then.
+	pub(crate) fn recursion_time(&self) -> Option<std::time::Duration> {
+		let stats = self.stats.as_ref()?;
+
+		// unbound only reports the average since it was started, so the recent average is derived from the change in the total.
+		let recursion_time_avg = match self.stats_and_previous() {
+			Some((stats, stats_previous)) if stats.recursive_replies > stats_previous.recursive_replies => {
+				let total = stats.recursion_time_avg * stats.recursive_replies as f64;
+				let total_previous = stats_previous.recursion_time_avg * stats_previous.recursive_replies as f64;
+				(total - total_previous).max(0.) / (stats.recursive_replies - stats_previous.recursive_replies) as f64
+			},
+			_ if stats.recursive_replies > 0 => stats.recursion_time_avg,
+			_ => return None,
+		};
+
+		Some(std::time::Duration::from_secs_f64(recursion_time_avg))
+	}
+
+	/// Counters of failed or dropped queries, and whether each of them increased since the previous update.
+	pub(crate) fn counters(&self) -> impl Iterator<Item = (&'static str, u64, bool)> {
+		let stats = self.stats.unwrap_or_default();
+		let stats_previous = self.stats_previous.unwrap_or(stats);
+
+		let counter = |name, value: u64, value_previous: u64| (name, value, value > value_previous);
+
+		stats.servfail.map(|servfail| counter("SERVFAIL", servfail, stats_previous.servfail.unwrap_or(servfail)))
+		.into_iter()
+		.chain([
+			counter("request list exceeded", stats.request_list_exceeded, stats_previous.request_list_exceeded),
+			counter("request list overwritten", stats.request_list_overwritten, stats_previous.request_list_overwritten),
+		])
+	}
+
+	fn stats_and_previous(&self) -> Option<(&crate::ssh_exec::unbound_control_stats::Stats, &crate::ssh_exec::unbound_control_stats::Stats)> {
+		Some((self.stats.as_ref()?, self.stats_previous.as_ref()?))
+	}
+}
@@ -0,0 +1,170 @@
+// WireGuard considers a session dead if it hasn't had a handshake in this long. Ref: REJECT_AFTER_TIME in the WireGuard paper
+const WIREGUARD_SESSION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(180);
+
+#[derive(Debug)]
+pub(crate) struct Tunnels {
+	inner: Vec<Tunnel>,
+
+	// `None` if there are no OpenVPN tunnels
+	openvpn_status_exec: Option<crate::ssh_exec::openvpn_status::Exec>,
+}
+
+impl Tunnels {
+	pub(crate) fn new(config: impl IntoIterator<Item = crate::pfconfig::VpnTunnel>) -> Self {
+		let inner =
+			config.into_iter()
+			.map(|config| Tunnel {
+				config,
+				is_up: false,
+				clients: None,
+				latest_handshake: None,
+				counters: Default::default(),
+				counters_previous: None,
+			})
+			.collect::<Vec<_>>();
+
+		let openvpn_ids: Vec<_> =
+			inner.iter()
+			.filter(|tunnel| matches!(tunnel.config.kind, crate::pfconfig::VpnKind::OpenVpnServer | crate::pfconfig::VpnKind::OpenVpnClient))
+			.map(|tunnel| &*tunnel.config.id)
+			.collect();
+		let openvpn_status_exec =
+			if openvpn_ids.is_empty() { None } else { Some(crate::ssh_exec::openvpn_status::Exec::new(openvpn_ids)) };
+
+		Tunnels {
+			inner,
+			openvpn_status_exec,
+		}
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	pub(crate) fn iter(&self) -> impl Iterator<Item = &Tunnel> {
+		self.inner.iter()
+	}
+
+	pub(crate) fn update(&mut self, session: &ssh2::Session) -> Result<(), crate::Error> {
+		let has_kind = |kind| self.inner.iter().any(|tunnel| tunnel.config.kind == kind);
+
+		let mut openvpn_statuses =
+			if let Some(openvpn_status_exec) = &self.openvpn_status_exec { openvpn_status_exec.run(session)? } else { Default::default() };
+
+		let ipsec_sas =
+			if has_kind(crate::pfconfig::VpnKind::Ipsec) { crate::ssh_exec::swanctl_list_sas::get_sas(session)? } else { vec![] };
+
+		let (wireguard_interfaces, wireguard_peers) =
+			if has_kind(crate::pfconfig::VpnKind::WireGuard) { crate::ssh_exec::wg_show_all_dump::get_peers(session)? } else { Default::default() };
+
+		for tunnel in &mut self.inner {
+			tunnel.counters_previous = tunnel.is_up.then_some(tunnel.counters);
+
+			match tunnel.config.kind {
+				crate::pfconfig::VpnKind::OpenVpnServer | crate::pfconfig::VpnKind::OpenVpnClient => {
+					let status = openvpn_statuses.remove(&tunnel.config.id).unwrap_or_default();
+					tunnel.is_up = status.is_connected;
+					tunnel.clients =
+						if tunnel.config.kind == crate::pfconfig::VpnKind::OpenVpnServer { Some(status.clients.len()) } else { None };
+					tunnel.latest_handshake = None;
+					tunnel.counters = Counters { received_bytes: status.bytes_received, sent_bytes: status.bytes_sent };
+				},
+
+				crate::pfconfig::VpnKind::Ipsec => {
+					// A phase 1 with multiple phase 2s can have its child SAs split across multiple connections, like `con1_0` and `con1_1`
+					let sas: Vec<_> =
+						ipsec_sas.iter()
+						.filter(|sa| sa.is_established && (
+							sa.name == tunnel.config.id ||
+							sa.name.strip_prefix(&*tunnel.config.id).is_some_and(|suffix| suffix.starts_with('_'))
+						))
+						.collect();
+					tunnel.is_up = !sas.is_empty();
+					tunnel.clients = None;
+					tunnel.latest_handshake = sas.iter().filter_map(|sa| sa.established).min();
+					tunnel.counters = Counters {
+						received_bytes: sas.iter().map(|sa| sa.bytes_received).sum(),
+						sent_bytes: sas.iter().map(|sa| sa.bytes_sent).sum(),
+					};
+				},
+
+				crate::pfconfig::VpnKind::WireGuard => {
+					let peers: Vec<_> = wireguard_peers.iter().filter(|peer| peer.interface == tunnel.config.id).collect();
+					let num_connected_peers =
+						peers.iter()
+						.filter(|peer| peer.latest_handshake.is_some_and(|latest_handshake| latest_handshake < WIREGUARD_SESSION_TIMEOUT))
+						.count();
+					tunnel.is_up = wireguard_interfaces.contains(&tunnel.config.id) && num_connected_peers > 0;
+					tunnel.clients = Some(num_connected_peers);
+					tunnel.latest_handshake = peers.iter().filter_map(|peer| peer.latest_handshake).min();
+					tunnel.counters = Counters {
+						received_bytes: peers.iter().map(|peer| peer.bytes_received).sum(),
+						sent_bytes: peers.iter().map(|peer| peer.bytes_sent).sum(),
+					};
+				},
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct Tunnel {
+	config: crate::pfconfig::VpnTunnel,
+
+	pub(crate) is_up: bool,
+
+	// Connected OpenVPN clients, or WireGuard peers with a current session
+	pub(crate) clients: Option<usize>,
+
+	// Time since the IPsec SA was established, or since the most recent WireGuard handshake
+	pub(crate) latest_handshake: Option<std::time::Duration>,
+
+	counters: Counters,
+
+	// `None` if the tunnel was down as of the previous update
+	counters_previous: Option<Counters>,
+}
+
+impl Tunnel {
+	pub(crate) fn description(&self) -> &str {
+		&self.config.description
+	}
+
+	pub(crate) fn kind(&self) -> &'static str {
+		match self.config.kind {
+			crate::pfconfig::VpnKind::OpenVpnServer => "OpenVPN server",
+			crate::pfconfig::VpnKind::OpenVpnClient => "OpenVPN client",
+			crate::pfconfig::VpnKind::Ipsec => "IPsec",
+			crate::pfconfig::VpnKind::WireGuard => "WireGuard",
+		}
+	}
+
+	/// What `latest_handshake` is the time since, for display
+	pub(crate) fn latest_handshake_kind(&self) -> &'static str {
+		match self.config.kind {
+			crate::pfconfig::VpnKind::Ipsec => "established",
+			_ => "handshake",
+		}
+	}
+
+	pub(crate) fn speed(&self, time_since_previous: std::time::Duration) -> Option<(f32, f32)> {
+		if !self.is_up {
+			return None;
+		}
+
+		let counters_previous = self.counters_previous?;
+
+		// Counters go backwards when a client disconnects or the tunnel reconnects, so don't let that show up as a negative rate.
+		let received_speed = crate::per_second(self.counters.received_bytes, counters_previous.received_bytes, time_since_previous) * 8.;
+		let sent_speed = crate::per_second(self.counters.sent_bytes, counters_previous.sent_bytes, time_since_previous) * 8.;
+		Some((received_speed, sent_speed))
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+	received_bytes: u64,
+	sent_bytes: u64,
+}
@@ -0,0 +1,211 @@
+#[derive(Debug)]
+pub(crate) struct Pool {
+	pub(crate) name: String,
+	zpool_status_exec: crate::ssh_exec::zpool_status::Exec,
+
+	pub(crate) health: String,
+	pub(crate) size: u64,
+	pub(crate) allocated: u64,
+	pub(crate) fragmentation_percent: Option<u64>,
+
+	// Of the pool's root dataset, which accounts for redundancy unlike `size` and `allocated`
+	pub(crate) used: u64,
+	pub(crate) available: u64,
+
+	pub(crate) scan: Scan,
+}
+
+#[derive(Debug)]
+pub(crate) enum Scan {
+	None,
+	InProgress,
+	Finished { errors: u64, days_ago: Option<u64> },
+	Other(String),
+}
+
+impl Pool {
+	pub(crate) fn get_all(session: &ssh2::Session) -> Result<Box<[Self]>, crate::Error> {
+		let mut result: Box<[_]> =
+			crate::ssh_exec::zpool_list::get_pools(session)?
+			.into_iter()
+			.map(|crate::ssh_exec::zpool_list::Pool { name, .. }| {
+				let zpool_status_exec = crate::ssh_exec::zpool_status::Exec::new(&name);
+				Pool {
+					name,
+					zpool_status_exec,
+
+					health: String::new(),
+					size: 0,
+					allocated: 0,
+					fragmentation_percent: None,
+
+					used: 0,
+					available: 0,
+
+					scan: Scan::None,
+				}
+			})
+			.collect();
+		result.sort_by(|pool1, pool2| pool1.name.cmp(&pool2.name));
+		Ok(result)
+	}
+
+	pub(crate) fn update_all(pools: &mut [Self], session: &ssh2::Session) -> Result<(), crate::Error> {
+		if pools.is_empty() {
+			return Ok(());
+		}
+
+		for pool in &mut *pools {
+			"MISSING".clone_into(&mut pool.health);
+		}
+
+		for crate::ssh_exec::zpool_list::Pool { name, size, allocated, fragmentation_percent, health } in crate::ssh_exec::zpool_list::get_pools(session)? {
+			if let Some(pool) = pools.iter_mut().find(|pool| pool.name == name) {
+				pool.health = health;
+				pool.size = size;
+				pool.allocated = allocated;
+				pool.fragmentation_percent = fragmentation_percent;
+			}
+		}
+
+		for crate::ssh_exec::zfs_list::Dataset { name, used, available } in crate::ssh_exec::zfs_list::get_root_datasets(session)? {
+			if let Some(pool) = pools.iter_mut().find(|pool| pool.name == name) {
+				pool.used = used;
+				pool.available = available;
+			}
+		}
+
+		let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?;
+		let today = now.as_secs() / 86400;
+
+		for pool in pools {
+			let scan = pool.zpool_status_exec.run(session)?;
+			pool.scan = scan.as_ref().map_or(Scan::None, |scan| Scan::parse(scan, today));
+		}
+
+		Ok(())
+	}
+}
+
+impl Scan {
+	fn parse(s: &str, today: u64) -> Self {
+		// none requested
+		// scrub in progress since Sun Mar 29 00:00:05 2020
+		// scrub repaired 0B in 00:00:05 with 0 errors on Sun Mar 29 00:00:05 2020
+		// scrub repaired 0 in 0h0m with 0 errors on Sun Mar 29 00:00:05 2020
+
+		if s == "none requested" {
+			return Scan::None;
+		}
+
+		if s.starts_with("scrub in progress") {
+			return Scan::InProgress;
+		}
+
+		if s.starts_with("scrub repaired") {
+			let errors = s.split(" with ").nth(1).and_then(|s| s.split(' ').next()).and_then(|errors| errors.parse().ok());
+			if let Some(errors) = errors {
+				let days_ago = s.split(" on ").nth(1).and_then(parse_ctime_days).map(|day| today.saturating_sub(day));
+				return Scan::Finished { errors, days_ago };
+			}
+		}
+
+		Scan::Other(s.to_owned())
+	}
+}
+
+// Parses a `Sun Mar 29 00:00:05 2020` timestamp into the number of days since the Unix epoch.
+fn parse_ctime_days(s: &str) -> Option<u64> {
+	let mut parts = s.split_whitespace();
+	let _weekday = parts.next()?;
+	let month = parts.next()?;
+	let month = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"].iter().position(|&m| m == month)? as u64 + 1;
+	let day: u64 = parts.next()?.parse().ok()?;
+	let _time = parts.next()?;
+	let year: u64 = parts.next()?.parse().ok()?;
+
+	// Ref: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year / 400;
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	(era * 146_097 + day_of_era).checked_sub(719_468)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ArcStats {
+	pub(crate) max_bytes: u64,
+
+	hits_previous: u64,
+	hits: u64,
+
+	misses_previous: u64,
+	misses: u64,
+}
+
+impl ArcStats {
+	/// Parses `kstat.zfs.misc.arcstats.c_max`, `hits` and `misses`
+	pub(crate) fn update(&mut self, reader: &mut impl std::io::Read) -> Result<(), crate::Error> {
+		self.max_bytes = crate::Parse::parse(reader)?;
+
+		self.hits_previous = self.hits;
+		self.hits = crate::Parse::parse(reader)?;
+
+		self.misses_previous = self.misses;
+		self.misses = crate::Parse::parse(reader)?;
+
+		Ok(())
+	}
+
+	/// The percentage of ARC lookups since the previous update that were hits.
+	pub(crate) fn hit_ratio_percent(&self) -> Option<f32> {
+		if self.hits_previous == 0 && self.misses_previous == 0 {
+			return None;
+		}
+
+		let hits = self.hits.saturating_sub(self.hits_previous);
+		let misses = self.misses.saturating_sub(self.misses_previous);
+		if hits + misses == 0 {
+			return None;
+		}
+
+		Some(hits as f32 * 100. / (hits + misses) as f32)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	// 2020-03-29
+	const DAY: u64 = 18350;
+
+	#[test]
+	fn parse_ctime_days() {
+		assert_eq!(super::parse_ctime_days("Sun Mar 29 00:00:05 2020"), Some(DAY));
+		assert_eq!(super::parse_ctime_days("Thu Jan  1 00:00:00 1970"), Some(0));
+		assert_eq!(super::parse_ctime_days("Sat Feb 29 12:00:00 2020"), Some(DAY - 29));
+		assert_eq!(super::parse_ctime_days("Sun Foo 29 00:00:05 2020"), None);
+	}
+
+	#[test]
+	fn parse_scan() {
+		assert!(matches!(super::Scan::parse("none requested", DAY + 3), super::Scan::None));
+
+		assert!(matches!(super::Scan::parse("scrub in progress since Sun Mar 29 00:00:05 2020", DAY + 3), super::Scan::InProgress));
+
+		assert!(matches!(
+			super::Scan::parse("scrub repaired 0B in 00:00:05 with 0 errors on Sun Mar 29 00:00:05 2020", DAY + 3),
+			super::Scan::Finished { errors: 0, days_ago: Some(3) },
+		));
+
+		assert!(matches!(
+			super::Scan::parse("scrub repaired 0 in 0h0m with 2 errors on Sun Mar 29 00:00:05 2020", DAY),
+			super::Scan::Finished { errors: 2, days_ago: Some(0) },
+		));
+
+		assert!(matches!(
+			super::Scan::parse("resilvered 1.2G in 00:10:00 with 0 errors on Sun Mar 29 00:00:05 2020", DAY),
+			super::Scan::Other(s) if s.starts_with("resilvered"),
+		));
+	}
+}